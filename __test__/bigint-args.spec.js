@@ -0,0 +1,16 @@
+import test from 'ava'
+import { Connection, Machine } from '..'
+import { TEST_URI, domainXml } from './helpers'
+
+test('BigInt arguments outside the u64 range throw instead of returning null', (t) => {
+  const conn = Connection.open(TEST_URI)
+  const machine = Machine.createXml(conn, domainXml('bigint-args'), 0)
+  t.truthy(machine)
+
+  t.throws(() => machine.setMemory(-1n), { message: /negative value not allowed/ })
+  t.throws(() => machine.setMaxMemory(2n ** 64n), { message: /exceeds u64 range/ })
+  t.throws(() => machine.blockResize('vda', -4096n, 0), { message: /negative value not allowed/ })
+
+  machine.destroy()
+  conn.close()
+})
@@ -0,0 +1,40 @@
+import test from 'ava'
+import { mkdtempSync, rmSync, writeFileSync } from 'fs'
+import { tmpdir } from 'os'
+import { join } from 'path'
+import { Connection, Machine } from '..'
+
+const CLONES = 10
+
+const nodeXml = `
+  <node>
+    <domain type='test'>
+      <name>clone-fixture</name>
+      <memory unit='MiB'>128</memory>
+      <os>
+        <type>hvm</type>
+      </os>
+    </domain>
+  </node>
+`
+
+test('machines share the connection instead of reopening its URI', (t) => {
+  const dir = mkdtempSync(join(tmpdir(), 'libvirt-node-'))
+  const file = join(dir, 'node.xml')
+  writeFileSync(file, nodeXml)
+
+  const conn = Connection.open(`test://${file}`)
+  t.truthy(conn)
+  // Once the driver file is gone the URI can only be opened that one time,
+  // so any reopen while building a Machine would fail.
+  rmSync(dir, { recursive: true })
+
+  for (let i = 0; i < CLONES; i++) {
+    const machine = Machine.lookupByName(conn, 'clone-fixture')
+    t.truthy(machine)
+    t.is(machine.getName(), 'clone-fixture')
+  }
+  t.true(conn.isAlive())
+
+  conn.close()
+})
@@ -0,0 +1,14 @@
+export const TEST_URI = 'test:///default'
+
+// Minimal test-driver domain; `devices` is spliced in after `<memory>`,
+// e.g. `<vcpu current='1'>4</vcpu>`.
+export const domainXml = (name, devices = '') => `
+  <domain type='test'>
+    <name>${name}</name>
+    <memory unit='MiB'>128</memory>
+    ${devices}
+    <os>
+      <type>hvm</type>
+    </os>
+  </domain>
+`
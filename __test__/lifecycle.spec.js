@@ -0,0 +1,57 @@
+import test from 'ava'
+import { readdirSync } from 'fs'
+import { Connection, Machine } from '..'
+import { TEST_URI, domainXml } from './helpers'
+
+const openFds = () => readdirSync('/proc/self/fd').length
+
+test('freeing one handle does not invalidate other handles to the same domain', (t) => {
+  const conn = Connection.open(TEST_URI)
+  t.truthy(conn)
+
+  const created = Machine.createXml(conn, domainXml('lifecycle-free'), 0)
+  t.truthy(created)
+  const lookedUp = Machine.lookupByName(conn, 'lifecycle-free')
+  t.truthy(lookedUp)
+
+  t.is(created.free(), 0)
+  t.is(lookedUp.getName(), 'lifecycle-free')
+
+  lookedUp.destroy()
+  conn.close()
+})
+
+test('operations on a stale handle return null instead of crashing', (t) => {
+  const conn = Connection.open(TEST_URI)
+  const machine = Machine.createXml(conn, domainXml('lifecycle-stale'), 0)
+  t.truthy(machine)
+
+  // A transient domain disappears once destroyed, leaving the handle stale.
+  t.not(machine.destroy(), null)
+  t.is(machine.getInfo(), null)
+  t.is(machine.getXmlDesc(), null)
+  t.is(Machine.lookupByName(conn, 'lifecycle-stale'), null)
+
+  conn.close()
+})
+
+test('repeated lookups do not leak handles', (t) => {
+  if (process.platform !== 'linux') {
+    t.pass('fd accounting relies on /proc')
+    return
+  }
+  const conn = Connection.open(TEST_URI)
+  const machine = Machine.createXml(conn, domainXml('lifecycle-leak'), 0)
+  t.truthy(machine)
+
+  const before = openFds()
+  for (let i = 0; i < 1000; i++) {
+    const handle = Machine.lookupByName(conn, 'lifecycle-leak')
+    t.truthy(handle)
+    handle.free()
+  }
+  t.is(openFds(), before)
+
+  machine.destroy()
+  conn.close()
+})
@@ -0,0 +1,17 @@
+import test from 'ava'
+import { Connection, Machine } from '..'
+import { TEST_URI, domainXml } from './helpers'
+
+test('numOfNodeDevices counts node devices, not defined domains', (t) => {
+  const conn = Connection.open(TEST_URI)
+  const machine = Machine.defineXml(conn, domainXml('node-devices-count'))
+  t.truthy(machine)
+
+  const devices = conn.listAllNodeDevices()
+  t.true(devices.length > 0)
+  t.is(conn.numOfNodeDevices(), devices.length)
+  t.not(conn.numOfNodeDevices(), conn.numOfDefinedDomains())
+
+  machine.undefine()
+  conn.close()
+})
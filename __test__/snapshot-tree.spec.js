@@ -0,0 +1,30 @@
+import test from 'ava'
+import { Connection, Machine, VirDomainSnapshotListFlags, VirDomainUndefineFlags } from '..'
+import { TEST_URI, domainXml } from './helpers'
+
+const snapshotXml = (name) => `<domainsnapshot><name>${name}</name></domainsnapshot>`
+
+test('snapshots expose their parent/child tree', (t) => {
+  const conn = Connection.open(TEST_URI)
+  const machine = Machine.defineXml(conn, domainXml('snapshot-tree'))
+  t.truthy(machine)
+
+  // Each snapshot becomes the current one, so they form the chain a -> b -> c.
+  const a = machine.snapshotCreateXml(snapshotXml('a'), 0)
+  const b = machine.snapshotCreateXml(snapshotXml('b'), 0)
+  const c = machine.snapshotCreateXml(snapshotXml('c'), 0)
+  t.truthy(a && b && c)
+
+  t.deepEqual(a.listAllChildren(0).map((s) => s.getName()), ['b'])
+  t.is(a.numChildren(0), 1)
+  // For children listings the ROOTS bit means "all descendants".
+  const descendants = a.listAllChildren(VirDomainSnapshotListFlags.VirDomainSnapshotListRoots)
+  t.deepEqual(descendants.map((s) => s.getName()).sort(), ['b', 'c'])
+  t.is(a.numChildren(VirDomainSnapshotListFlags.VirDomainSnapshotListRoots), 2)
+
+  t.is(c.getParent().getName(), 'b')
+  t.deepEqual(c.listAllChildren(0), [])
+
+  machine.undefineFlags(VirDomainUndefineFlags.VirDomainUndefineSnapshotsMetadata)
+  conn.close()
+})
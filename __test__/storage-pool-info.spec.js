@@ -0,0 +1,72 @@
+import test from 'ava'
+import { mkdtempSync, rmSync, writeFileSync } from 'fs'
+import { tmpdir } from 'os'
+import { join } from 'path'
+import { Connection, StoragePool, VirStoragePoolState } from '..'
+import { TEST_URI } from './helpers'
+
+const GiB = 1024n ** 3n
+
+test('getInfoTyped reports the pool sizes as exact BigInts', (t) => {
+  const conn = Connection.open(TEST_URI)
+  const pool = StoragePool.lookupByName(conn, 'default-pool')
+  t.truthy(pool)
+
+  const before = pool.getInfoTyped()
+  t.is(before.state, VirStoragePoolState.VirStoragePoolRunning)
+  t.is(typeof before.capacity, 'bigint')
+  t.is(typeof before.allocation, 'bigint')
+  t.is(typeof before.available, 'bigint')
+  t.is(before.allocation + before.available, before.capacity)
+
+  const allocation = GiB + 1n
+  const vol = pool.createVolume('pool-info.img', 2n * GiB, 'raw', 0, allocation)
+  t.truthy(vol)
+
+  const after = pool.getInfoTyped()
+  t.is(after.allocation - before.allocation, allocation)
+  t.is(after.available, before.available - allocation)
+
+  vol.delete(0)
+  conn.close()
+})
+
+// All three sizes are above 2^63, beyond both i64 and a JS number's exact range.
+const HUGE_CAPACITY = 2n ** 64n - 1024n
+const HUGE_ALLOCATION = 2n ** 63n + 512n
+const HUGE_AVAILABLE = 2n ** 63n + 1024n
+
+const hugePoolNodeXml = `
+  <node>
+    <pool type='dir'>
+      <name>huge-pool</name>
+      <capacity unit='bytes'>${HUGE_CAPACITY}</capacity>
+      <allocation unit='bytes'>${HUGE_ALLOCATION}</allocation>
+      <available unit='bytes'>${HUGE_AVAILABLE}</available>
+      <source/>
+      <target>
+        <path>/huge-pool</path>
+      </target>
+    </pool>
+  </node>
+`
+
+test('getInfoTyped does not truncate sizes above 8 EiB', (t) => {
+  const dir = mkdtempSync(join(tmpdir(), 'libvirt-node-'))
+  const file = join(dir, 'node.xml')
+  writeFileSync(file, hugePoolNodeXml)
+
+  const conn = Connection.open(`test://${file}`)
+  rmSync(dir, { recursive: true })
+  t.truthy(conn)
+
+  const pool = StoragePool.lookupByName(conn, 'huge-pool')
+  t.truthy(pool)
+
+  const info = pool.getInfoTyped()
+  t.is(info.capacity, HUGE_CAPACITY)
+  t.is(info.allocation, HUGE_ALLOCATION)
+  t.is(info.available, HUGE_AVAILABLE)
+
+  conn.close()
+})
@@ -0,0 +1,24 @@
+import test from 'ava'
+import { Connection, Machine, VirDomainVcpuFlags } from '..'
+import { TEST_URI, domainXml } from './helpers'
+
+const vcpuDomainXml = (name) => domainXml(name, "<vcpu current='1'>4</vcpu>")
+
+test('setVcpusFlags changes the live vCPU count and reads it back', (t) => {
+  const conn = Connection.open(TEST_URI)
+  const machine = Machine.defineXml(conn, vcpuDomainXml('vcpus-live'))
+  t.truthy(machine)
+  machine.create()
+
+  t.is(machine.getMaxVcpusConfig(), 4)
+  t.is(machine.getVcpusFlags(VirDomainVcpuFlags.VirDomainVcpuLive), 1)
+
+  t.true(machine.setVcpusFlags(3, VirDomainVcpuFlags.VirDomainVcpuLive))
+  t.is(machine.getVcpusFlags(VirDomainVcpuFlags.VirDomainVcpuLive), 3)
+  // Only the running domain changed, the definition keeps its original count.
+  t.is(machine.getVcpusFlags(VirDomainVcpuFlags.VirDomainVcpuConfig), 1)
+
+  machine.destroy()
+  machine.undefine()
+  conn.close()
+})
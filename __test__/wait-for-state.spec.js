@@ -0,0 +1,40 @@
+import test from 'ava'
+import { Connection, Machine, VirDomainState } from '..'
+import { TEST_URI, domainXml } from './helpers'
+
+test('waitForState returns true once the domain reaches the state', (t) => {
+  const conn = Connection.open(TEST_URI)
+  const machine = Machine.createXml(conn, domainXml('wait-paused'), 0)
+  t.truthy(machine)
+
+  t.true(machine.waitForState(VirDomainState.VirDomainRunning, 1000, 10))
+  machine.suspend()
+  t.true(machine.waitForState(VirDomainState.VirDomainPaused, 1000, 10))
+
+  machine.destroy()
+  conn.close()
+})
+
+test('waitForState returns false when the timeout elapses', (t) => {
+  const conn = Connection.open(TEST_URI)
+  const machine = Machine.createXml(conn, domainXml('wait-timeout'), 0)
+  t.truthy(machine)
+
+  const started = Date.now()
+  t.false(machine.waitForState(VirDomainState.VirDomainShutoff, 100, 10))
+  t.true(Date.now() - started >= 100)
+
+  machine.destroy()
+  conn.close()
+})
+
+test('waitForState returns null for a domain that no longer exists', (t) => {
+  const conn = Connection.open(TEST_URI)
+  const machine = Machine.createXml(conn, domainXml('wait-gone'), 0)
+  t.truthy(machine)
+
+  machine.destroy()
+  t.is(machine.waitForState(VirDomainState.VirDomainRunning, 100, 10), null)
+
+  conn.close()
+})
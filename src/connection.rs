@@ -6,15 +6,75 @@ use crate::machine::Machine;
 #[napi]
 pub struct Connection {
   con: Connect,
+  /// URI the connection was opened with, kept so `reconnect` still works
+  /// once the remote end is gone and `virConnectGetURI` fails.
+  uri: String,
 }
 
+/// Outcome of a bulk autostart update for a single object.
+#[napi]
+pub struct AutostartResult {
+  /// Name of the domain, network or storage pool.
+  pub name: String,
+  /// Whether the autostart flag was applied successfully.
+  pub ok: bool,
+}
+
+/// Traffic counters of one interface of a running domain.
+#[napi]
+pub struct DomainInterfaceStats {
+  /// Name of the domain owning the interface.
+  pub domain: String,
+  /// MAC address of the interface.
+  pub mac: String,
+  /// Host side device name the stats were read from, e.g. `vnet0`.
+  pub device: String,
+  pub stats: crate::machine::InterfaceStats,
+}
+
+/// One memory statistic of the host or of a NUMA cell.
+#[napi]
+pub struct NodeMemoryStat {
+  /// Name of the statistic: `total`, `free`, `buffers` or `cached`.
+  pub field: String,
+  /// Value in kibibytes.
+  pub value: napi::bindgen_prelude::BigInt,
+}
+
+/// The CPUs of the host and which of them are online.
+#[napi]
+pub struct CpuMap {
+  /// Number of CPUs present on the host.
+  pub cpus: u32,
+  /// Number of online CPUs.
+  pub online: u32,
+  /// Whether each CPU is online, indexed by CPU number.
+  pub map: Vec<bool>,
+}
+
+/// The security driver of the host.
+#[napi]
+pub struct SecurityModel {
+  /// Name of the security model, e.g. `selinux` or `apparmor`; empty if none is active.
+  pub model: String,
+  /// Domain of interpretation of the model's labels.
+  pub doi: String,
+}
+
+/// A libvirt version number split into its components.
+#[napi]
+pub struct Version {
+  pub major: u32,
+  pub minor: u32,
+  pub release: u32,
+}
+
+/// Clones share the libvirt connection: only a reference is taken on the
+/// handle (`virConnectRef`), so events registered on one clone fire for all
+/// of them and remote URIs are not authenticated again.
 impl Clone for Connection {
   fn clone(&self) -> Self {
-    let uri = self.con.get_uri().expect("Failed to get URI for cloning");
-    let new_connection = Connect::open(Some(&uri)).expect("Failed to clone connection");
-    Connection {
-      con: new_connection,
-    }
+    self.share()
   }
 }
 
@@ -24,11 +84,23 @@ impl Connection {
     return &self.con;
   }
 
+  /// Returns a wrapper sharing the same libvirt connection.
+  ///
+  /// This only takes an extra reference on the underlying handle
+  /// (`virConnectRef`), so no new connection is opened.
+  pub fn share(&self) -> Connection {
+    Connection {
+      con: self.con.clone(),
+      uri: self.uri.clone(),
+    }
+  }
+
   #[napi]
   pub fn open(name: String) -> Option<Connection> {
+    crate::events::ensure_event_loop();
     let con = Connect::open(Some(&name));
     match con {
-      Ok(connection) => Some(Self { con: connection }),
+      Ok(connection) => Some(Self { con: connection, uri: name }),
       Err(_) => None,
     }
   }
@@ -49,10 +121,95 @@ impl Connection {
     }
   }
 
+  /// Reopen the connection if it is no longer alive.
+  ///
+  /// A remote connection dies when the daemon restarts or keep-alive
+  /// messages go unanswered (see `setKeepAlive`); from then on every call
+  /// returns `null`. This reopens the URI the connection was opened with.
+  ///
+  /// Machines and other objects obtained before the reconnect still hold
+  /// the dead connection and must be looked up again.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `false` - If the connection was alive and left untouched.
+  /// * `true` - If the connection was dead and has been reopened.
+  /// * `null` - If it was dead and could not be reopened.
+  ///
+  /// # Example (in JavaScript)
+  ///
+  /// A long-running daemon can wrap its calls so a dropped connection is
+  /// reopened once before giving up:
+  ///
+  /// ```javascript
+  /// const { Connection } = require('your-node-package');
+  ///
+  /// const conn = Connection.open('qemu+ssh://host/system');
+  /// conn.setKeepAlive(5, 3);
+  ///
+  /// function withRetry(fn) {
+  ///   const result = fn(conn);
+  ///   if (result !== null || !conn.reconnect()) {
+  ///     return result;
+  ///   }
+  ///   return fn(conn);
+  /// }
+  ///
+  /// const names = withRetry((c) => c.listAllDomains()?.map((m) => m.getName()) ?? null);
+  /// ```
   #[napi]
-  pub fn get_sys_info(&self, flags: u32) -> Option<String> {
+  pub fn reconnect(&mut self) -> Option<bool> {
+    if let Ok(true) = self.con.is_alive() {
+      return Some(false);
+    }
+    match Connect::open(Some(&self.uri)) {
+      Ok(connection) => {
+        let mut old = std::mem::replace(&mut self.con, connection);
+        let _ = old.close();
+        Some(true)
+      }
+      Err(_) => None,
+    }
+  }
+
+  /// Get the last error raised on this connection.
+  ///
+  /// Unlike `Error.lastError()`, which reads the error of the calling
+  /// thread, this is tied to the connection, so it stays correct when calls
+  /// on several connections run concurrently on the worker threads.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `Error` - The last error of the connection.
+  /// * `null` - If no error happened since the connection was opened or
+  ///   `resetLastError` was called.
+  ///
+  /// # Example (in JavaScript)
+  ///
+  /// ```javascript
+  /// if (conn.domainLookupByName('missing') === null) {
+  ///   const err = conn.getLastError();
+  ///   console.error(err?.message);
+  ///   conn.resetLastError();
+  /// }
+  /// ```
+  #[napi]
+  pub fn get_last_error(&self) -> Option<crate::error::Error> {
+    unsafe { crate::error::Error::from_raw(virt::sys::virConnGetLastError(self.con.as_ptr())) }
+  }
+
+  /// Clear the last error of this connection, see `getLastError`.
+  #[napi]
+  pub fn reset_last_error(&self) {
+    unsafe { virt::sys::virConnResetLastError(self.con.as_ptr()) }
+  }
+
+  #[napi]
+  pub fn get_sys_info(&self, flags: Option<u32>) -> Option<String> {
     // Implement
-    match self.con.get_sys_info(flags) {
+    match self.con.get_sys_info(flags.unwrap_or(0)) {
       Ok(info) => Some(info),
       Err(_) => None,
     }
@@ -139,15 +296,15 @@ impl Connection {
     }
   }
 
+  /// List domains on this connection, filtered by `flags` (see
+  /// VirConnectListAllDomainsFlags; `0` lists every domain).
   #[napi]
-  pub fn list_all_domains(&self, flags: u32) -> Option<Vec<Machine>> {
-    match self.con.list_all_domains(flags) {
+  pub fn list_all_domains(&self, flags: Option<u32>) -> Option<Vec<Machine>> {
+    match self.con.list_all_domains(flags.unwrap_or(0)) {
       Ok(domains) => {
         let mut machines = Vec::new();
         for domain in domains {
-          // Clone the domain to avoid moving out of the shared reference
-          let domain_clone = domain;
-          machines.push(Machine::from_domain(domain_clone, &self));
+          machines.push(Machine::from_shared_domain(domain, self.share()));
         }
         Some(machines)
       }
@@ -155,9 +312,42 @@ impl Connection {
     }
   }
 
+  /// Look up a domain by name on this connection.
+  ///
+  /// Returns `null` if no such domain exists; see `Error.lastError()` for details.
   #[napi]
-  pub fn list_all_networks(&self, flags: u32) -> Option<Vec<crate::network::Network>> {
-    match self.con.list_all_networks(flags) {
+  pub fn domain_lookup_by_name(&self, name: String) -> Option<Machine> {
+    match virt::domain::Domain::lookup_by_name(&self.con, &name) {
+      Ok(domain) => Some(Machine::from_shared_domain(domain, self.share())),
+      Err(_) => None,
+    }
+  }
+
+  /// Look up a running domain by its ID on this connection.
+  ///
+  /// Returns `null` if no such domain exists; see `Error.lastError()` for details.
+  #[napi]
+  pub fn domain_lookup_by_id(&self, id: u32) -> Option<Machine> {
+    match virt::domain::Domain::lookup_by_id(&self.con, id) {
+      Ok(domain) => Some(Machine::from_shared_domain(domain, self.share())),
+      Err(_) => None,
+    }
+  }
+
+  /// Look up a domain by its UUID string on this connection.
+  ///
+  /// Returns `null` if no such domain exists; see `Error.lastError()` for details.
+  #[napi]
+  pub fn domain_lookup_by_uuid_string(&self, uuid: String) -> Option<Machine> {
+    match virt::domain::Domain::lookup_by_uuid_string(&self.con, &uuid) {
+      Ok(domain) => Some(Machine::from_shared_domain(domain, self.share())),
+      Err(_) => None,
+    }
+  }
+
+  #[napi]
+  pub fn list_all_networks(&self, flags: Option<u32>) -> Option<Vec<crate::network::Network>> {
+    match self.con.list_all_networks(flags.unwrap_or(0)) {
       Ok(networks) => {
         let mut network_wrappers = Vec::new();
         for network in networks {
@@ -170,8 +360,8 @@ impl Connection {
   }
 
   #[napi]
-  pub fn list_all_interfaces(&self, flags: u32) -> Option<Vec<crate::interface::Interface>> {
-    match self.con.list_all_interfaces(flags) {
+  pub fn list_all_interfaces(&self, flags: Option<u32>) -> Option<Vec<crate::interface::Interface>> {
+    match self.con.list_all_interfaces(flags.unwrap_or(0)) {
       Ok(interfaces) => {
         let mut interface_wrappers = Vec::new();
         for interface in interfaces {
@@ -186,9 +376,9 @@ impl Connection {
   #[napi]
   pub fn list_all_node_devices(
     &self,
-    flags: u32,
+    flags: Option<u32>,
   ) -> Option<Vec<crate::node_device::NodeDevice>> {
-    match self.con.list_all_node_devices(flags) {
+    match self.con.list_all_node_devices(flags.unwrap_or(0)) {
       Ok(node_devices) => {
         let mut node_device_wrappers = Vec::new();
         for node_device in node_devices {
@@ -201,8 +391,8 @@ impl Connection {
   }
 
   #[napi]
-  pub fn list_all_secrets(&self, flags: u32) -> Option<Vec<crate::secret::Secret>> {
-    match self.con.list_all_secrets(flags) {
+  pub fn list_all_secrets(&self, flags: Option<u32>) -> Option<Vec<crate::secret::Secret>> {
+    match self.con.list_all_secrets(flags.unwrap_or(0)) {
       Ok(secrets) => {
         let mut secret_wrappers = Vec::new();
         for secret in secrets {
@@ -214,12 +404,14 @@ impl Connection {
     }
   }
 
+  /// List storage pools on this connection, filtered by `flags` (see
+  /// VirConnectListAllStoragePoolsFlags; `0` lists every pool).
   #[napi]
   pub fn list_all_storage_pools(
     &self,
-    flags: u32,
+    flags: Option<u32>,
   ) -> Option<Vec<crate::storage_pool::StoragePool>> {
-    match self.con.list_all_storage_pools(flags) {
+    match self.con.list_all_storage_pools(flags.unwrap_or(0)) {
       Ok(storage_pools) => {
         let mut storage_pool_wrappers = Vec::new();
         for storage_pool in storage_pools {
@@ -234,8 +426,8 @@ impl Connection {
   }
 
   #[napi]
-  pub fn list_all_nw_filters(&self, flags: u32) -> Option<Vec<crate::nw_filter::NWFilter>> {
-    match self.con.list_all_nw_filters(flags) {
+  pub fn list_all_nw_filters(&self, flags: Option<u32>) -> Option<Vec<crate::nw_filter::NWFilter>> {
+    match self.con.list_all_nw_filters(flags.unwrap_or(0)) {
       Ok(nw_filters) => {
         let mut nw_filter_wrappers = Vec::new();
         for nw_filter in nw_filters {
@@ -351,11 +543,12 @@ impl Connection {
     }
   }
 
+  /// Number of node devices known to the host, of any capability.
   #[napi]
   pub fn num_of_node_devices(&self) -> Option<u32> {
-    match self.con.num_of_defined_domains() {
-      Ok(num) => Some(num),
-      Err(_) => None,
+    match unsafe { virt::sys::virNodeNumOfDevices(self.con.as_ptr(), std::ptr::null(), 0) } {
+      -1 => None,
+      num => Some(num as u32),
     }
   }
 
@@ -391,6 +584,34 @@ impl Connection {
     }
   }
 
+  /// Get the hostname of the host this connection is attached to.
+  #[napi]
+  pub fn get_hostname(&self) -> Option<String> {
+    match self.con.get_hostname() {
+      Ok(hostname) => Some(hostname),
+      Err(_) => None,
+    }
+  }
+
+  /// Get the canonical URI of the connection, as resolved by libvirt.
+  #[napi]
+  pub fn get_uri(&self) -> Option<String> {
+    match self.con.get_uri() {
+      Ok(uri) => Some(uri),
+      Err(_) => None,
+    }
+  }
+
+  /// Get the version of libvirt used by the daemon this connection talks to,
+  /// packed as `major * 1000000 + minor * 1000 + release`. See `decodeVersion`.
+  #[napi]
+  pub fn get_lib_version(&self) -> Option<u32> {
+    match self.con.get_lib_version() {
+      Ok(version) => Some(version),
+      Err(_) => None,
+    }
+  }
+
   #[napi]
   pub fn compare_cpu(
     &self,
@@ -411,6 +632,133 @@ impl Connection {
     }
   }
 
+  /// Get the number of free pages of each size on a range of NUMA cells.
+  ///
+  /// # Arguments
+  ///
+  /// * `page_sizes` - Page sizes to query, in KiB, e.g. `[4, 2048, 1048576]`.
+  /// * `start_cell` - First NUMA cell to query, or `-1` for the whole host.
+  /// * `cell_count` - Number of cells to query (1 with `start_cell` `-1`).
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `BigInt[]` - The free page counts, grouped by cell: the count of
+  ///   `page_sizes[j]` on cell `start_cell + i` is at index `i * page_sizes.length + j`.
+  /// * `null` - If there is an error.
+  ///
+  /// # Example (in JavaScript)
+  ///
+  /// ```javascript
+  /// const [small, huge] = conn.getFreePages([4, 2048], 0, 1);
+  /// console.log(`cell 0: ${huge} free 2 MiB pages`);
+  /// ```
+  #[napi]
+  pub fn get_free_pages(
+    &self,
+    page_sizes: Vec<u32>,
+    start_cell: i32,
+    cell_count: u32,
+  ) -> Option<Vec<napi::bindgen_prelude::BigInt>> {
+    let mut pages = page_sizes;
+    let mut counts = vec![0u64; pages.len() * cell_count as usize];
+    let filled = unsafe {
+      virt::sys::virNodeGetFreePages(
+        self.con.as_ptr(),
+        pages.len() as u32,
+        pages.as_mut_ptr(),
+        start_cell,
+        cell_count,
+        counts.as_mut_ptr(),
+        0,
+      )
+    };
+    if filled < 0 {
+      return None;
+    }
+    counts.truncate(filled as usize);
+    Some(counts.into_iter().map(Into::into).collect())
+  }
+
+  /// Get memory statistics of the host or of one NUMA cell.
+  ///
+  /// # Arguments
+  ///
+  /// * `cell_num` - The NUMA cell to query, or `-1` for the whole host.
+  /// * `flags` - Currently unused, pass 0.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `NodeMemoryStat[]` - The statistics, in KiB.
+  /// * `null` - If there is an error.
+  #[napi]
+  pub fn get_memory_stats(&self, cell_num: i32, flags: u32) -> Option<Vec<NodeMemoryStat>> {
+    let conn = self.con.as_ptr();
+    let mut nparams: std::os::raw::c_int = 0;
+    if unsafe { virt::sys::virNodeGetMemoryStats(conn, cell_num, std::ptr::null_mut(), &mut nparams, flags) } < 0 {
+      return None;
+    }
+    let mut params: Vec<virt::sys::virNodeMemoryStats> = (0..nparams)
+      .map(|_| unsafe { std::mem::zeroed() })
+      .collect();
+    if unsafe { virt::sys::virNodeGetMemoryStats(conn, cell_num, params.as_mut_ptr(), &mut nparams, flags) } < 0 {
+      return None;
+    }
+    Some(
+      params
+        .iter()
+        .take(nparams as usize)
+        .map(|param| NodeMemoryStat {
+          field: unsafe { std::ffi::CStr::from_ptr(param.field.as_ptr()) }
+            .to_string_lossy()
+            .into_owned(),
+          value: param.value.into(),
+        })
+        .collect(),
+    )
+  }
+
+  /// Get the CPUs present on the host and which of them are online.
+  ///
+  /// `map` has one entry per host CPU, in the order expected by the cpumap
+  /// argument of `Machine.pinVcpu` and `Machine.pinEmulator`.
+  #[napi]
+  pub fn get_cpu_map(&self) -> Option<CpuMap> {
+    let mut cpumap: *mut std::os::raw::c_uchar = std::ptr::null_mut();
+    let mut online: std::os::raw::c_uint = 0;
+    let cpus = unsafe { virt::sys::virNodeGetCPUMap(self.con.as_ptr(), &mut cpumap, &mut online, 0) };
+    if cpus < 0 {
+      return None;
+    }
+    let map = (0..cpus as usize)
+      .map(|cpu| unsafe { *cpumap.add(cpu / 8) } & (1 << (cpu % 8)) != 0)
+      .collect();
+    unsafe { crate::machine::free_libvirt_memory(cpumap) };
+    Some(CpuMap {
+      cpus: cpus as u32,
+      online,
+      map,
+    })
+  }
+
+  /// Get the security model (SELinux, AppArmor, ...) used to confine domains on the host.
+  #[napi]
+  pub fn get_security_model(&self) -> Option<SecurityModel> {
+    let mut secmodel: virt::sys::virSecurityModel = unsafe { std::mem::zeroed() };
+    if unsafe { virt::sys::virNodeGetSecurityModel(self.con.as_ptr(), &mut secmodel) } < 0 {
+      return None;
+    }
+    Some(SecurityModel {
+      model: unsafe { std::ffi::CStr::from_ptr(secmodel.model.as_ptr()) }
+        .to_string_lossy()
+        .into_owned(),
+      doi: unsafe { std::ffi::CStr::from_ptr(secmodel.doi.as_ptr()) }
+        .to_string_lossy()
+        .into_owned(),
+    })
+  }
+
   #[napi]
   pub fn get_node_info(&self) -> Option<crate::node_info::NodeInfo> {
     match self.con.get_node_info() {
@@ -440,6 +788,36 @@ impl Connection {
     }
   }
 
+  /// Convert a native hypervisor configuration to domain XML and define the
+  /// resulting domain, in one step.
+  ///
+  /// # Arguments
+  ///
+  /// * `format` - Format of the native configuration, e.g. `qemu-argv` or `lxc-tools`.
+  /// * `native_config` - The native configuration itself.
+  /// * `flags` - Currently unused, pass 0.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `Machine` - The newly defined (inactive) domain.
+  /// * `null` - If the configuration could not be converted or the domain not defined.
+  ///
+  /// # Example (in JavaScript)
+  ///
+  /// ```javascript
+  /// const argv = 'LC_ALL=C /usr/bin/qemu-system-x86_64 -name legacy -m 1024 -hda /var/lib/images/legacy.img';
+  /// const machine = conn.defineDomainFromNative('qemu-argv', argv, 0);
+  /// ```
+  #[napi]
+  pub fn define_domain_from_native(&self, format: String, native_config: String, flags: u32) -> Option<Machine> {
+    let xml = self.con.domain_xml_from_native(&format, &native_config, flags).ok()?;
+    match virt::domain::Domain::define_xml(&self.con, &xml) {
+      Ok(domain) => Some(Machine::from_shared_domain(domain, self.share())),
+      Err(_) => None,
+    }
+  }
+
   #[napi]
   pub fn domain_xml_to_native(
     &self,
@@ -453,6 +831,16 @@ impl Connection {
     }
   }
 
+  /// Get the capabilities XML of the host: CPU model and features, NUMA
+  /// topology and the guest architectures and machine types it supports.
+  #[napi]
+  pub fn get_capabilities(&self) -> Option<String> {
+    match self.con.get_capabilities() {
+      Ok(xml) => Some(xml),
+      Err(_) => None,
+    }
+  }
+
   #[napi]
   pub fn get_domain_capabilities(
     &self,
@@ -468,6 +856,13 @@ impl Connection {
     }
   }
 
+  /// Query statistics for all domains in a single call.
+  ///
+  /// # Arguments
+  ///
+  /// * `stats` - Stats groups to collect, OR-ed VirDomainStatsTypes. `0` collects
+  ///   every group the hypervisor supports.
+  /// * `flags` - Domain filters and behaviour, OR-ed VirConnectGetAllDomainStatsFlags.
   #[napi]
   pub fn get_all_domain_stats(
     &self,
@@ -486,6 +881,47 @@ impl Connection {
     }
   }
 
+  /// Query statistics for all domains and parse every record.
+  ///
+  /// Same as `getAllDomainStats` followed by `toJson` on each record.
+  ///
+  /// # Arguments
+  ///
+  /// * `stats` - Stats groups to collect, OR-ed VirDomainStatsTypes.
+  /// * `flags` - Domain filters and behaviour, OR-ed VirConnectGetAllDomainStatsFlags.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `ParsedDomainStats[]` - One entry per domain, with its name and nested stats.
+  /// * `null` - If the stats could not be retrieved.
+  ///
+  /// # Example (in JavaScript)
+  ///
+  /// ```javascript
+  /// const { Connection, VirDomainStatsTypes } = require('your-node-package');
+  ///
+  /// const conn = Connection.open('qemu:///system');
+  /// const groups = VirDomainStatsTypes.VirDomainStatsState | VirDomainStatsTypes.VirDomainStatsBalloon;
+  /// for (const { name, stats } of conn.getAllDomainStatsParsed(groups, 0)) {
+  ///   console.log(name, stats.state.state, stats.balloon?.current);
+  /// }
+  /// ```
+  #[napi]
+  pub fn get_all_domain_stats_parsed(
+    &self,
+    stats: u32,
+    flags: u32,
+  ) -> Option<Vec<crate::domain_stats_record::ParsedDomainStats>> {
+    match self.con.get_all_domain_stats(stats, flags) {
+      Ok(stats) => stats
+        .into_iter()
+        .map(|stat| crate::domain_stats_record::DomainStatsRecord::from_stat(stat).to_parsed())
+        .collect(),
+      Err(_) => None,
+    }
+  }
+
   #[napi]
   pub fn baseline_cpu(
     &self,
@@ -511,4 +947,255 @@ impl Connection {
       Err(_) => None,
     }
   }
+
+  /// Set the autostart flag on every persistent domain.
+  ///
+  /// Returns one entry per domain telling whether the update succeeded,
+  /// or `null` if the domains could not be listed.
+  #[napi]
+  pub fn set_all_domains_autostart(&self, autostart: bool) -> Option<Vec<AutostartResult>> {
+    match self.con.list_all_domains(virt::sys::VIR_CONNECT_LIST_DOMAINS_PERSISTENT) {
+      Ok(domains) => Some(
+        domains
+          .iter()
+          .map(|domain| AutostartResult {
+            name: domain.get_name().unwrap_or_default(),
+            ok: domain.set_autostart(autostart).is_ok(),
+          })
+          .collect(),
+      ),
+      Err(_) => None,
+    }
+  }
+
+  /// Set the autostart flag on every persistent network.
+  ///
+  /// Returns one entry per network telling whether the update succeeded,
+  /// or `null` if the networks could not be listed.
+  #[napi]
+  pub fn set_all_networks_autostart(&self, autostart: bool) -> Option<Vec<AutostartResult>> {
+    match self.con.list_all_networks(virt::sys::VIR_CONNECT_LIST_NETWORKS_PERSISTENT) {
+      Ok(networks) => Some(
+        networks
+          .iter()
+          .map(|network| AutostartResult {
+            name: network.get_name().unwrap_or_default(),
+            ok: network.set_autostart(autostart).is_ok(),
+          })
+          .collect(),
+      ),
+      Err(_) => None,
+    }
+  }
+
+  /// Set the autostart flag on every persistent storage pool.
+  ///
+  /// Returns one entry per pool telling whether the update succeeded,
+  /// or `null` if the pools could not be listed.
+  #[napi]
+  pub fn set_all_pools_autostart(&self, autostart: bool) -> Option<Vec<AutostartResult>> {
+    match self.con.list_all_storage_pools(virt::sys::VIR_CONNECT_LIST_STORAGE_POOLS_PERSISTENT) {
+      Ok(pools) => Some(
+        pools
+          .iter()
+          .map(|pool| AutostartResult {
+            name: pool.get_name().unwrap_or_default(),
+            ok: pool.set_autostart(autostart).is_ok(),
+          })
+          .collect(),
+      ),
+      Err(_) => None,
+    }
+  }
+
+  /// Subscribe to node device events, e.g. a USB device being plugged in or removed.
+  ///
+  /// # Arguments
+  ///
+  /// * `callback` - Called with `{ deviceName, eventId, event, detail }` for every event.
+  /// * `event_id` - Which events to listen for. Use VirNodeDeviceEventID enum. Defaults to lifecycle events.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * The callback id, to be passed to `deregisterNodeDeviceEvent`.
+  /// * `null` - If libvirt refused the registration.
+  ///
+  /// # Example (in JavaScript)
+  ///
+  /// ```javascript
+  /// const { Connection, VirNodeDeviceEventID, VirNodeDeviceEventLifecycleType } = require('your-node-package');
+  ///
+  /// const conn = Connection.open('qemu:///system');
+  /// const id = conn.onNodeDeviceEvent((ev) => {
+  ///   if (ev.event === VirNodeDeviceEventLifecycleType.VirNodeDeviceEventCreated) {
+  ///     console.log(`device attached: ${ev.deviceName}`);
+  ///   }
+  /// }, VirNodeDeviceEventID.VirNodeDeviceEventIdLifecycle);
+  /// ```
+  #[napi]
+  pub fn on_node_device_event(
+    &self,
+    callback: napi::JsFunction,
+    event_id: Option<u32>,
+  ) -> napi::Result<Option<i32>> {
+    let callback: crate::events::NodeDeviceEventCallback = callback.create_threadsafe_function(
+      0,
+      |ctx: napi::threadsafe_function::ThreadSafeCallContext<crate::events::NodeDeviceEvent>| Ok(vec![ctx.value]),
+    )?;
+    Ok(crate::events::register_node_device_event(
+      self.con.as_ptr(),
+      event_id.unwrap_or(virt::sys::VIR_NODE_DEVICE_EVENT_ID_LIFECYCLE as u32),
+      callback,
+    ))
+  }
+
+  /// Remove a subscription made with `onNodeDeviceEvent`.
+  ///
+  /// The callback is not invoked anymore once this returns, even for events
+  /// that were already queued. Returns `0` on success or `null` if the id is unknown.
+  #[napi]
+  pub fn deregister_node_device_event(&self, callback_id: i32) -> Option<i32> {
+    crate::events::deregister_node_device_event(self.con.as_ptr(), callback_id)
+  }
+
+  /// Collect the interface counters of every running domain in one call.
+  ///
+  /// Interfaces are discovered from each domain's live XML. Domains that
+  /// stop while being walked and interfaces without a host side device
+  /// are skipped.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * One entry per interface of every running domain.
+  /// * `null` - If the running domains could not be listed.
+  ///
+  /// # Example (in JavaScript)
+  ///
+  /// ```javascript
+  /// const { Connection } = require('your-node-package');
+  ///
+  /// const conn = Connection.open('qemu:///system');
+  /// for (const entry of conn.collectAllInterfaceStats()) {
+  ///   console.log(`${entry.domain} ${entry.mac}: rx=${entry.stats.rxBytes} tx=${entry.stats.txBytes}`);
+  /// }
+  /// ```
+  #[napi]
+  pub fn collect_all_interface_stats(&self) -> Option<Vec<DomainInterfaceStats>> {
+    let domains = match self.con.list_all_domains(virt::sys::VIR_CONNECT_LIST_DOMAINS_ACTIVE) {
+      Ok(domains) => domains,
+      Err(_) => return None,
+    };
+    let mut result = Vec::new();
+    for domain in domains {
+      let name = match domain.get_name() {
+        Ok(name) => name,
+        Err(_) => continue,
+      };
+      let desc = match domain.get_xml_desc(0).ok().and_then(|xml| crate::xml::parse(&xml)) {
+        Some(desc) => desc,
+        None => continue,
+      };
+      let interfaces = match desc.child("devices") {
+        Some(devices) => devices.children_named("interface"),
+        None => continue,
+      };
+      for interface in interfaces {
+        let device = match interface.child("target").and_then(|target| target.attr("dev")) {
+          Some(device) => device,
+          None => continue,
+        };
+        if let Ok(stats) = domain.interface_stats(device) {
+          result.push(DomainInterfaceStats {
+            domain: name.clone(),
+            mac: interface
+              .child("mac")
+              .and_then(|mac| mac.attr("address"))
+              .unwrap_or_default()
+              .to_string(),
+            device: device.to_string(),
+            stats: crate::machine::InterfaceStats::from(stats),
+          });
+        }
+      }
+    }
+    Some(result)
+  }
+
+  /// Subscribe to lifecycle events of every domain: started, stopped, crashed, ...
+  ///
+  /// # Arguments
+  ///
+  /// * `callback` - Called with `{ domainName, event, detail }` for every event.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * The callback id, to be passed to `deregisterDomainEvent`.
+  /// * `null` - If libvirt refused the registration.
+  ///
+  /// # Example (in JavaScript)
+  ///
+  /// ```javascript
+  /// const { Connection, VirDomainEventType } = require('your-node-package');
+  ///
+  /// const conn = Connection.open('qemu:///system');
+  /// const id = conn.registerDomainEventLifecycle((ev) => {
+  ///   if (ev.event === VirDomainEventType.VirDomainEventCrashed) {
+  ///     console.error(`${ev.domainName} crashed`);
+  ///   }
+  /// });
+  /// // later
+  /// conn.deregisterDomainEvent(id);
+  /// ```
+  #[napi]
+  pub fn register_domain_event_lifecycle(&self, callback: napi::JsFunction) -> napi::Result<Option<i32>> {
+    let callback: crate::events::DomainLifecycleCallback = callback.create_threadsafe_function(
+      0,
+      |ctx: napi::threadsafe_function::ThreadSafeCallContext<crate::events::DomainLifecycleEvent>| Ok(vec![ctx.value]),
+    )?;
+    Ok(crate::events::register_domain_lifecycle_event(self.con.as_ptr(), callback))
+  }
+
+  /// Remove a subscription made with `registerDomainEventLifecycle`.
+  ///
+  /// The callback is not invoked anymore once this returns, even for events
+  /// that were already queued. Returns `0` on success or `null` if the id is unknown.
+  #[napi]
+  pub fn deregister_domain_event(&self, callback_id: i32) -> Option<i32> {
+    crate::events::deregister_domain_event(self.con.as_ptr(), callback_id)
+  }
+}
+
+/// Get the version of the libvirt client library this module is linked
+/// against, packed as `major * 1000000 + minor * 1000 + release`.
+#[napi]
+pub fn get_version() -> Option<u32> {
+  match Connect::get_version() {
+    Ok(version) => Some(version),
+    Err(_) => None,
+  }
+}
+
+/// Split a packed libvirt version number into its components.
+///
+/// # Example (in JavaScript)
+///
+/// ```javascript
+/// const { Connection, decodeVersion } = require('your-node-package');
+///
+/// const conn = Connection.open('qemu:///system');
+/// const { major, minor } = decodeVersion(conn.getLibVersion());
+/// if (major > 8 || (major === 8 && minor >= 5)) {
+///   // use a feature that needs libvirt 8.5
+/// }
+/// ```
+#[napi]
+pub fn decode_version(version: u32) -> Version {
+  Version {
+    major: version / 1_000_000,
+    minor: version / 1_000 % 1_000,
+    release: version % 1_000,
+  }
 }
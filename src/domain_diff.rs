@@ -0,0 +1,193 @@
+//! Semantic comparison of two domain XML documents.
+
+use serde_json::{json, Map, Value};
+
+use crate::xml::{self, Element};
+
+/// Elements libvirt only adds to live XML; they never describe a config change.
+const RUNTIME_ONLY: &[&str] = &["alias"];
+
+/// Compare two domain XML documents and describe what changed between them.
+///
+/// Whitespace, attribute order and element order are ignored. Top level
+/// elements (memory, vcpu, os, ...) are compared as a whole; devices are
+/// matched by identity (disk target, interface MAC, controller type and
+/// index, ...) so a device that only moved is not reported.
+///
+/// # Arguments
+///
+/// * `a` - The original domain XML, e.g. the persistent definition.
+/// * `b` - The domain XML to compare against, e.g. the live definition.
+///
+/// # Returns
+///
+/// This function returns:
+/// * An object `{ identical, changed, devices: { added, removed, changed } }`.
+///   `changed` lists `{ element, from, to }` for top level elements and
+///   `devices` lists `{ key, device }` / `{ key, from, to }` entries, where
+///   every element is rendered as `{ name, attributes, text, children }`.
+/// * `null` - If one of the documents is not well formed XML.
+///
+/// # Example (in JavaScript)
+///
+/// ```javascript
+/// const { Connection, Machine, VirDomainXMLFlags, diffDomainXml } = require('your-node-package');
+///
+/// const conn = Connection.open('qemu:///system');
+/// const machine = Machine.lookupByName(conn, 'your-domain-name');
+/// const persistent = machine.getXmlDesc(VirDomainXMLFlags.VirDomainXMLInactive);
+/// const live = machine.getXmlDesc();
+/// const diff = diffDomainXml(persistent, live);
+/// if (!diff.identical) {
+///   console.log('pending changes', diff.changed, diff.devices);
+/// }
+/// ```
+#[napi]
+pub fn diff_domain_xml(a: String, b: String) -> Option<Value> {
+  let a = xml::parse(&a)?;
+  let b = xml::parse(&b)?;
+
+  let mut changed = Vec::new();
+  let not_devices = |element: &Element| element.name != "devices";
+  for (key, from, to) in pair_up(keyed_children(&a, not_devices), keyed_children(&b, not_devices)) {
+    if from != to {
+      changed.push(json!({ "element": key, "from": from, "to": to }));
+    }
+  }
+
+  let mut added = Vec::new();
+  let mut removed = Vec::new();
+  let mut devices_changed = Vec::new();
+  for (key, from, to) in pair_up(devices(&a), devices(&b)) {
+    match (from, to) {
+      (Value::Null, device) => added.push(json!({ "key": key, "device": device })),
+      (device, Value::Null) => removed.push(json!({ "key": key, "device": device })),
+      (from, to) => {
+        if from != to {
+          devices_changed.push(json!({ "key": key, "from": from, "to": to }));
+        }
+      }
+    }
+  }
+
+  let identical = changed.is_empty() && added.is_empty() && removed.is_empty() && devices_changed.is_empty();
+  Some(json!({
+    "identical": identical,
+    "changed": changed,
+    "devices": {
+      "added": added,
+      "removed": removed,
+      "changed": devices_changed,
+    },
+  }))
+}
+
+/// Joins two keyed lists into `(key, a, b)` triples, `Null` standing in for a missing side.
+fn pair_up(a: Vec<(String, Value)>, b: Vec<(String, Value)>) -> Vec<(String, Value, Value)> {
+  let mut b: Vec<Option<(String, Value)>> = b.into_iter().map(Some).collect();
+  let mut pairs = Vec::new();
+  for (key, from) in a {
+    let to = b
+      .iter_mut()
+      .find(|entry| entry.as_ref().map_or(false, |(other, _)| *other == key))
+      .and_then(|entry| entry.take())
+      .map_or(Value::Null, |(_, value)| value);
+    pairs.push((key, from, to));
+  }
+  for (key, to) in b.into_iter().flatten() {
+    pairs.push((key, Value::Null, to));
+  }
+  pairs
+}
+
+/// Canonical children of `parent` accepted by `keep`, keyed by name and occurrence.
+fn keyed_children(parent: &Element, keep: impl Fn(&Element) -> bool) -> Vec<(String, Value)> {
+  let mut keyed: Vec<(String, Value)> = Vec::new();
+  for child in parent.children.iter().filter(|&child| keep(child)) {
+    let seen = keyed.iter().filter(|(key, _)| key.split('#').next() == Some(child.name.as_str())).count();
+    let key = if seen == 0 {
+      child.name.clone()
+    } else {
+      format!("{}#{}", child.name, seen)
+    };
+    keyed.push((key, canonical(child)));
+  }
+  keyed
+}
+
+fn devices(domain: &Element) -> Vec<(String, Value)> {
+  let mut keyed: Vec<(String, Value)> = Vec::new();
+  if let Some(devices) = domain.child("devices") {
+    for device in &devices.children {
+      let key = device_key(device);
+      let seen = keyed.iter().filter(|(other, _)| other.split('#').next() == Some(key.as_str())).count();
+      let key = if seen == 0 { key } else { format!("{}#{}", key, seen) };
+      keyed.push((key, canonical(device)));
+    }
+  }
+  keyed
+}
+
+/// A key identifying `device` across two definitions of the same domain.
+fn device_key(device: &Element) -> String {
+  let identity = match device.name.as_str() {
+    "disk" | "filesystem" => device
+      .child("target")
+      .and_then(|target| target.attr("dev").or_else(|| target.attr("dir")))
+      .map(str::to_string),
+    "interface" => device.child("mac").and_then(|mac| mac.attr("address")).map(str::to_string),
+    "controller" => Some(format!(
+      "{}:{}",
+      device.attr("type").unwrap_or_default(),
+      device.attr("index").unwrap_or_default()
+    )),
+    "hostdev" => device.child("source").map(source_identity),
+    _ => device.attr("type").map(str::to_string),
+  };
+  match identity {
+    Some(identity) => format!("{}:{}", device.name, identity),
+    None => device.name.clone(),
+  }
+}
+
+/// Flattens the address or vendor/product ids of a hostdev source.
+fn source_identity(source: &Element) -> String {
+  let mut parts: Vec<String> = Vec::new();
+  for child in &source.children {
+    let mut attributes: Vec<String> = child
+      .attributes
+      .iter()
+      .map(|(key, value)| format!("{}={}", key, value))
+      .collect();
+    attributes.sort();
+    parts.push(format!("{}({})", child.name, attributes.join(",")));
+  }
+  parts.join(";")
+}
+
+/// Renders `element` as JSON with sorted attributes and children, dropping runtime-only elements.
+fn canonical(element: &Element) -> Value {
+  let mut attributes = Map::new();
+  let mut sorted: Vec<&(String, String)> = element.attributes.iter().collect();
+  sorted.sort();
+  for (key, value) in sorted {
+    attributes.insert(key.clone(), Value::String(value.clone()));
+  }
+
+  let mut children: Vec<Value> = element
+    .children
+    .iter()
+    .filter(|child| !RUNTIME_ONLY.contains(&child.name.as_str()))
+    .map(canonical)
+    .collect();
+  children.sort_by_cached_key(|child| child.to_string());
+
+  let mut value = Map::new();
+  value.insert("name".to_string(), Value::String(element.name.clone()));
+  value.insert("attributes".to_string(), Value::Object(attributes));
+  if !element.text().is_empty() {
+    value.insert("text".to_string(), Value::String(element.text().to_string()));
+  }
+  value.insert("children".to_string(), Value::Array(children));
+  Value::Object(value)
+}
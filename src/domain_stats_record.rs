@@ -1,10 +1,38 @@
+use std::ffi::CStr;
+
 use virt;
 
+use crate::typed_params::{self, TypedParamValue};
+
 #[napi]
 pub struct DomainStatsRecord {
     stat: virt::domain::DomainStatsRecord,
 }
 
+/// Memory dirty rate reported by the DIRTYRATE stats group.
+#[napi]
+pub struct DirtyRate {
+    /// The calculated memory dirty rate in MiB/s.
+    pub megabytes_per_second: Option<i64>,
+    /// The status of the calculation, see VirDomainDirtyRateStatus.
+    pub calc_status: Option<i32>,
+    /// The start time of the calculation, in seconds.
+    pub calc_start_time: Option<i64>,
+    /// The period of the calculation, in seconds.
+    pub calc_period: Option<i32>,
+}
+
+/// A domain stats record with its fields already parsed, as returned by
+/// `Connection.getAllDomainStatsParsed`.
+#[napi(object)]
+pub struct ParsedDomainStats {
+    /// Name of the domain the stats belong to.
+    pub name: String,
+    /// The fields of the record as a nested object, see `DomainStatsRecord.toJson`.
+    pub stats: serde_json::Value,
+}
+
+#[napi]
 impl DomainStatsRecord {
     pub fn from_stat(stat: virt::domain::DomainStatsRecord) -> Self {
         Self { stat }
@@ -13,4 +41,101 @@ impl DomainStatsRecord {
     pub fn get(&self) -> &virt::domain::DomainStatsRecord {
         &self.stat
     }
+
+    pub(crate) fn params(&self) -> Vec<(String, TypedParamValue)> {
+        if self.stat.ptr.is_null() {
+            return Vec::new();
+        }
+        unsafe {
+            let record = &*self.stat.ptr;
+            typed_params::from_raw(record.params, record.nparams)
+        }
+    }
+
+    /// Get the name of the domain the record belongs to.
+    #[napi]
+    pub fn get_domain_name(&self) -> Option<String> {
+        if self.stat.ptr.is_null() {
+            return None;
+        }
+        unsafe {
+            let name = virt::sys::virDomainGetName((*self.stat.ptr).dom);
+            if name.is_null() {
+                return None;
+            }
+            Some(CStr::from_ptr(name).to_string_lossy().into_owned())
+        }
+    }
+
+    /// Get every field of the record as a nested object.
+    ///
+    /// Field names are split on `.`, so `cpu.time` is found at
+    /// `stats.cpu.time` and `vcpu.0.state` at `stats.vcpu['0'].state`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns:
+    /// * `object` - The fields of the record. Empty if the record has none.
+    /// * `null` - If the record is not backed by libvirt data.
+    ///
+    /// # Example (in JavaScript)
+    ///
+    /// ```javascript
+    /// const { Connection } = require('your-node-package');
+    ///
+    /// const conn = Connection.open('qemu:///system');
+    /// for (const record of conn.getAllDomainStats(0, 0)) {
+    ///   const stats = record.toJson();
+    ///   console.log(record.getDomainName(), stats.cpu?.time, stats.balloon?.current);
+    /// }
+    /// ```
+    #[napi]
+    pub fn to_json(&self) -> Option<serde_json::Value> {
+        if self.stat.ptr.is_null() {
+            return None;
+        }
+        Some(typed_params::to_nested_json(&self.params()))
+    }
+
+    pub(crate) fn to_parsed(&self) -> Option<ParsedDomainStats> {
+        Some(ParsedDomainStats {
+            name: self.get_domain_name()?,
+            stats: self.to_json()?,
+        })
+    }
+
+    /// Get the memory dirty rate of the domain.
+    ///
+    /// Only available when the stats were requested with the DIRTYRATE
+    /// group and a calculation was started with `Machine.startDirtyRateCalc`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns:
+    /// * `DirtyRate` - If the record contains dirty rate fields.
+    /// * `null` - If the record has no dirty rate information.
+    #[napi]
+    pub fn get_dirty_rate(&self) -> Option<DirtyRate> {
+        DirtyRate::from_params(&self.params())
+    }
+}
+
+impl DirtyRate {
+    pub(crate) fn from_params(params: &[(String, TypedParamValue)]) -> Option<DirtyRate> {
+        if !params.iter().any(|(name, _)| name.starts_with("dirtyrate.")) {
+            return None;
+        }
+        Some(DirtyRate {
+            megabytes_per_second: typed_params::find(params, "dirtyrate.megabytes_per_second")
+                .and_then(|v| v.as_i64()),
+            calc_status: typed_params::find(params, "dirtyrate.calc_status")
+                .and_then(|v| v.as_i64())
+                .map(|v| v as i32),
+            calc_start_time: typed_params::find(params, "dirtyrate.calc_start_time")
+                .and_then(|v| v.as_i64()),
+            calc_period: typed_params::find(params, "dirtyrate.calc_period")
+                .and_then(|v| v.as_i64())
+                .map(|v| v as i32),
+        })
+    }
 }
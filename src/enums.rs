@@ -117,6 +117,23 @@ pub enum VirDomainMemoryModFlags {
     VirDomainMemMaximum = 4,
 }
 
+#[napi]
+#[repr(u32)]
+pub enum VirDomainVcpuFlags {
+    /// See virDomainModificationImpact
+    VirDomainVcpuCurrent = 0, // VIR_DOMAIN_AFFECT_CURRENT
+    /// See virDomainModificationImpact
+    VirDomainVcpuLive = 1, // VIR_DOMAIN_AFFECT_LIVE
+    /// See virDomainModificationImpact
+    VirDomainVcpuConfig = 2, // VIR_DOMAIN_AFFECT_CONFIG
+    /// Max rather than current count
+    VirDomainVcpuMaximum = 4,
+    /// Modify state of the cpu in the guest
+    VirDomainVcpuGuest = 8,
+    /// Make vcpus added hot(un)pluggable
+    VirDomainVcpuHotpluggable = 16,
+}
+
 #[napi]
 #[repr(u32)]
 pub enum VirStoragePoolCreateFlags {
@@ -130,9 +147,694 @@ pub enum VirStoragePoolCreateFlags {
     VirStoragePoolCreateWithBuildNoOverwrite = 4,
 }
 
+/// libvirt does not define any refresh flag yet; pass the default.
+#[napi]
+#[repr(u32)]
+pub enum VirStoragePoolRefreshFlags {
+    /// Default behavior
+    VirStoragePoolRefreshDefault = 0,
+}
+
+#[napi]
+#[repr(u32)]
+pub enum VirStoragePoolState {
+    /// Not running
+    VirStoragePoolInactive = 0,
+    /// Initializing pool, not available
+    VirStoragePoolBuilding = 1,
+    /// Running normally
+    VirStoragePoolRunning = 2,
+    /// Running degraded
+    VirStoragePoolDegraded = 3,
+    /// Running, but not accessible
+    VirStoragePoolInaccessible = 4,
+}
+
+#[napi]
+#[repr(u32)]
+pub enum VirStorageVolType {
+    /// Regular file based volumes
+    VirStorageVolFile = 0,
+    /// Block based volumes
+    VirStorageVolBlock = 1,
+    /// Directory-passthrough based volume
+    VirStorageVolDir = 2,
+    /// Network volumes like RBD (RADOS Block Device)
+    VirStorageVolNetwork = 3,
+    /// Network accessible directory that can contain other network volumes
+    VirStorageVolNetdir = 4,
+    /// Ploop based volumes
+    VirStorageVolPloop = 5,
+}
+
 #[napi]
 #[repr(u32)]
 pub enum VirStorageXMLFlags {
     /// Inactive
     VirStorageXMLInactive = 1,
-}
\ No newline at end of file
+}
+
+#[napi]
+#[repr(u32)]
+pub enum VirDomainBlockCommitFlags {
+    /// NULL base means next backing file, not whole chain
+    VirDomainBlockCommitShallow = 1,
+    /// Delete any files that are now invalid after their contents have been committed
+    VirDomainBlockCommitDelete = 2,
+    /// Allow a two-phase commit when top is the active layer
+    VirDomainBlockCommitActive = 4,
+    /// Keep the backing chain referenced using relative names
+    VirDomainBlockCommitRelative = 8,
+    /// Bandwidth is in bytes/s instead of MiB/s
+    VirDomainBlockCommitBandwidthBytes = 16,
+}
+
+#[napi]
+#[repr(u32)]
+pub enum VirDomainBlockPullFlags {
+    /// Bandwidth is in bytes/s instead of MiB/s
+    VirDomainBlockPullBandwidthBytes = 64,
+}
+
+#[napi]
+#[repr(u32)]
+pub enum VirDomainBlockCopyFlags {
+    /// Limit copy to top of source backing chain
+    VirDomainBlockCopyShallow = 1,
+    /// Reuse existing external file for a copy
+    VirDomainBlockCopyReuseExt = 2,
+    /// Don't force usage of recoverable job for the copy operation
+    VirDomainBlockCopyTransientJob = 4,
+    /// Force the copy job to not finish until guest writes reach the destination
+    VirDomainBlockCopySynchronousWrites = 8,
+}
+
+#[napi]
+#[repr(u32)]
+pub enum VirNodeDeviceEventID {
+    /// Device created, deleted, defined or undefined
+    VirNodeDeviceEventIdLifecycle = 0,
+    /// Device configuration or state changed
+    VirNodeDeviceEventIdUpdate = 1,
+}
+
+#[napi]
+#[repr(u32)]
+pub enum VirNodeDeviceEventLifecycleType {
+    /// Device appeared on the host
+    VirNodeDeviceEventCreated = 0,
+    /// Device disappeared from the host
+    VirNodeDeviceEventDeleted = 1,
+    /// Device was defined
+    VirNodeDeviceEventDefined = 2,
+    /// Device was undefined
+    VirNodeDeviceEventUndefined = 3,
+}
+
+#[napi]
+#[repr(u32)]
+pub enum VirDomainState {
+    /// No state
+    VirDomainNostate = 0,
+    /// The domain is running
+    VirDomainRunning = 1,
+    /// The domain is blocked on resource
+    VirDomainBlocked = 2,
+    /// The domain is paused by user
+    VirDomainPaused = 3,
+    /// The domain is being shut down
+    VirDomainShutdown = 4,
+    /// The domain is shut off
+    VirDomainShutoff = 5,
+    /// The domain is crashed
+    VirDomainCrashed = 6,
+    /// The domain is suspended by guest power management
+    VirDomainPmsuspended = 7,
+}
+
+#[napi]
+#[repr(u32)]
+pub enum VirVcpuState {
+    /// The virtual CPU is offline
+    VirVcpuOffline = 0,
+    /// The virtual CPU is running
+    VirVcpuRunning = 1,
+    /// The virtual CPU is blocked on resource
+    VirVcpuBlocked = 2,
+}
+
+#[napi]
+#[repr(u32)]
+pub enum VirDomainEventType {
+    /// The domain was defined
+    VirDomainEventDefined = 0,
+    /// The domain was undefined
+    VirDomainEventUndefined = 1,
+    /// The domain was started
+    VirDomainEventStarted = 2,
+    /// The domain was suspended
+    VirDomainEventSuspended = 3,
+    /// The domain was resumed
+    VirDomainEventResumed = 4,
+    /// The domain was stopped
+    VirDomainEventStopped = 5,
+    /// The domain was shut down
+    VirDomainEventShutdown = 6,
+    /// The domain was suspended by guest power management
+    VirDomainEventPmsuspended = 7,
+    /// The domain crashed
+    VirDomainEventCrashed = 8,
+}
+
+#[napi]
+#[repr(u32)]
+pub enum VirDomainSnapshotCreateFlags {
+    /// Restore or alter metadata
+    VirDomainSnapshotCreateRedefine = 1,
+    /// With redefine, make snapshot current
+    VirDomainSnapshotCreateCurrent = 2,
+    /// Make snapshot without remembering it
+    VirDomainSnapshotCreateNoMetadata = 4,
+    /// Stop running guest after snapshot
+    VirDomainSnapshotCreateHalt = 8,
+    /// Disk snapshot, not full system
+    VirDomainSnapshotCreateDiskOnly = 16,
+    /// Reuse any existing external files
+    VirDomainSnapshotCreateReuseExt = 32,
+    /// Use guest agent to quiesce all mounted file systems within the domain
+    VirDomainSnapshotCreateQuiesce = 64,
+    /// Atomically avoid partial changes
+    VirDomainSnapshotCreateAtomic = 128,
+    /// Create the snapshot while the guest is running
+    VirDomainSnapshotCreateLive = 256,
+    /// Validate the XML document against schema
+    VirDomainSnapshotCreateValidate = 512,
+}
+
+#[napi]
+#[repr(u32)]
+pub enum VirDomainSnapshotListFlags {
+    /// Filter by snapshots with no parents, when listing a domain. When
+    /// listing the children of a snapshot, list all descendants instead
+    VirDomainSnapshotListRoots = 1,
+    /// Filter by snapshots which have metadata
+    VirDomainSnapshotListMetadata = 2,
+    /// Filter by snapshots with no children
+    VirDomainSnapshotListLeaves = 4,
+    /// Filter by snapshots that have children
+    VirDomainSnapshotListNoLeaves = 8,
+    /// Filter by snapshots with no metadata
+    VirDomainSnapshotListNoMetadata = 16,
+    /// Filter by snapshots taken while guest was shut off
+    VirDomainSnapshotListInactive = 32,
+    /// Filter by snapshots taken while guest was active, and with memory state
+    VirDomainSnapshotListActive = 64,
+    /// Filter by snapshots taken while guest was active, but without memory state
+    VirDomainSnapshotListDiskOnly = 128,
+    /// Filter by snapshots stored internal to disk images
+    VirDomainSnapshotListInternal = 256,
+    /// Filter by snapshots that use files external to disk images
+    VirDomainSnapshotListExternal = 512,
+    /// Ensure parents occur before children in the resulting list
+    VirDomainSnapshotListTopological = 1024,
+}
+
+#[napi]
+#[repr(u32)]
+pub enum VirDomainSnapshotRevertFlags {
+    /// Run after revert
+    VirDomainSnapshotRevertRunning = 1,
+    /// Pause after revert
+    VirDomainSnapshotRevertPaused = 2,
+    /// Allow risky reverts
+    VirDomainSnapshotRevertForce = 4,
+    /// Re-initialize NVRAM from template
+    VirDomainSnapshotRevertResetNvram = 8,
+}
+
+#[napi]
+#[repr(u32)]
+pub enum VirDomainConsoleFlags {
+    /// Abort a (possibly) active console connection to force a new connection
+    VirDomainConsoleForce = 1,
+    /// Check if the console driver supports safe console operations
+    VirDomainConsoleSafe = 2,
+}
+
+#[napi]
+#[repr(u32)]
+pub enum VirStreamFlags {
+    /// Create a non-blocking stream
+    VirStreamNonblock = 1,
+}
+
+#[napi]
+#[repr(u32)]
+pub enum VirDomainJobType {
+    /// No job is active
+    VirDomainJobNone = 0,
+    /// Job with a finite completion time
+    VirDomainJobBounded = 1,
+    /// Job without a finite completion time
+    VirDomainJobUnbounded = 2,
+    /// Job has finished, but isn't cleaned up
+    VirDomainJobCompleted = 3,
+    /// Job hit error, but isn't cleaned up
+    VirDomainJobFailed = 4,
+    /// Job was aborted, but isn't cleaned up
+    VirDomainJobCancelled = 5,
+}
+
+#[napi]
+#[repr(u32)]
+pub enum VirDomainBlockJobType {
+    /// Placeholder
+    VirDomainBlockJobTypeUnknown = 0,
+    /// Block Pull (virDomainBlockPull, or virDomainBlockRebase without flags)
+    VirDomainBlockJobTypePull = 1,
+    /// Block Copy (virDomainBlockCopy, or virDomainBlockRebase with flags)
+    VirDomainBlockJobTypeCopy = 2,
+    /// Block Commit (virDomainBlockCommit without flags)
+    VirDomainBlockJobTypeCommit = 3,
+    /// Active Block Commit (virDomainBlockCommit with flags)
+    VirDomainBlockJobTypeActiveCommit = 4,
+    /// Backup (virDomainBackupBegin)
+    VirDomainBlockJobTypeBackup = 5,
+}
+
+#[napi]
+#[repr(u32)]
+pub enum VirDomainBlockJobAbortFlags {
+    /// Request only, do not wait for completion
+    VirDomainBlockJobAbortAsync = 1,
+    /// Pivot to new file when ending a copy or active commit job
+    VirDomainBlockJobAbortPivot = 2,
+}
+
+#[napi]
+#[repr(u32)]
+pub enum VirDomainGuestInfoTypes {
+    /// Return active users
+    VirDomainGuestInfoUsers = 1,
+    /// Return OS information
+    VirDomainGuestInfoOs = 2,
+    /// Return timezone information
+    VirDomainGuestInfoTimezone = 4,
+    /// Return hostname information
+    VirDomainGuestInfoHostname = 8,
+    /// Return filesystem information
+    VirDomainGuestInfoFilesystem = 16,
+    /// Return disk information
+    VirDomainGuestInfoDisks = 32,
+    /// Return network interfaces information
+    VirDomainGuestInfoInterfaces = 64,
+}
+
+#[napi]
+#[repr(u32)]
+pub enum VirKeycodeSet {
+    /// Linux key code set
+    VirKeycodeSetLinux = 0,
+    /// XT key code set
+    VirKeycodeSetXt = 1,
+    /// AT set 1 key code set
+    VirKeycodeSetAtset1 = 2,
+    /// AT set 2 key code set
+    VirKeycodeSetAtset2 = 3,
+    /// AT set 3 key code set
+    VirKeycodeSetAtset3 = 4,
+    /// OS-X key code set
+    VirKeycodeSetOsx = 5,
+    /// XT keyboard key code set
+    VirKeycodeSetXtKbd = 6,
+    /// USB HID key code set
+    VirKeycodeSetUsb = 7,
+    /// Windows key code set
+    VirKeycodeSetWin32 = 8,
+    /// QEMU QNum key code set
+    VirKeycodeSetQnum = 9,
+}
+
+#[napi]
+#[repr(u32)]
+pub enum VirDomainControlState {
+    /// Operational, ready to accept commands
+    VirDomainControlOk = 0,
+    /// Background job is running (can be monitored by virDomainGetJobInfo); only limited set of commands may be allowed
+    VirDomainControlJob = 1,
+    /// Occupied by a running command
+    VirDomainControlOccupied = 2,
+    /// Unusable, domain cannot be fully operated, possible reason is provided in the details field
+    VirDomainControlError = 3,
+}
+
+#[napi]
+#[repr(u32)]
+pub enum VirNodeSuspendTarget {
+    /// Suspend-to-RAM
+    VirNodeSuspendTargetMem = 0,
+    /// Suspend-to-Disk
+    VirNodeSuspendTargetDisk = 1,
+    /// Hybrid-Suspend
+    VirNodeSuspendTargetHybrid = 2,
+}
+
+#[napi]
+#[repr(u32)]
+pub enum VirDomainCoreDumpFlags {
+    /// Crash after dump
+    VirDumpCrash = 1,
+    /// Live dump
+    VirDumpLive = 2,
+    /// Avoid file system cache pollution
+    VirDumpBypassCache = 4,
+    /// Reset domain after dump finishes
+    VirDumpReset = 8,
+    /// Use dump-guest-memory
+    VirDumpMemoryOnly = 16,
+}
+
+#[napi]
+#[repr(u32)]
+pub enum VirDomainCoreDumpFormat {
+    /// Dump guest memory in raw format
+    VirDomainCoreDumpFormatRaw = 0,
+    /// Kdump-compressed format, with zlib compression
+    VirDomainCoreDumpFormatKdumpZlib = 1,
+    /// Kdump-compressed format, with lzo compression
+    VirDomainCoreDumpFormatKdumpLzo = 2,
+    /// Kdump-compressed format, with snappy compression
+    VirDomainCoreDumpFormatKdumpSnappy = 3,
+    /// Windows full crashdump format
+    VirDomainCoreDumpFormatWinDmp = 4,
+}
+
+#[napi]
+#[repr(u32)]
+pub enum VirDomainSaveRestoreFlags {
+    /// Avoid file system cache pollution
+    VirDomainSaveBypassCache = 1,
+    /// Favor running over paused
+    VirDomainSaveRunning = 2,
+    /// Favor paused over running
+    VirDomainSavePaused = 4,
+    /// Re-initialize NVRAM from template
+    VirDomainSaveResetNvram = 8,
+}
+
+#[napi]
+#[repr(u32)]
+pub enum VirDomainMigrateFlags {
+    /// Do not pause the domain during migration
+    VirMigrateLive = 1,
+    /// Direct source -> dest host control channel
+    VirMigratePeer2peer = 2,
+    /// Tunnel migration data over the libvirtd connection (requires VirMigratePeer2peer)
+    VirMigrateTunnelled = 4,
+    /// Persist the VM on the destination
+    VirMigratePersistDest = 8,
+    /// Undefine the VM on the source
+    VirMigrateUndefineSource = 16,
+    /// Pause on remote side
+    VirMigratePaused = 32,
+    /// Migration with non-shared storage with full disk copy
+    VirMigrateNonSharedDisk = 64,
+    /// Migration with non-shared storage with incremental copy
+    VirMigrateNonSharedInc = 128,
+    /// Protect for changing domain configuration through the whole migration process
+    VirMigrateChangeProtection = 256,
+    /// Force migration even if it is considered unsafe
+    VirMigrateUnsafe = 512,
+    /// Offline migrate, only the definition is transferred
+    VirMigrateOffline = 1024,
+    /// Compress data during migration
+    VirMigrateCompressed = 2048,
+    /// Abort migration on I/O errors happened during migration
+    VirMigrateAbortOnError = 4096,
+    /// Force convergence by throttling the guest vCPUs
+    VirMigrateAutoConverge = 8192,
+    /// RDMA memory pinning
+    VirMigrateRdmaPinAll = 16384,
+    /// Enable post-copy migration
+    VirMigratePostcopy = 32768,
+    /// Use TLS for native migration
+    VirMigrateTls = 65536,
+    /// Send memory pages to the destination host through several network connections
+    VirMigrateParallel = 131072,
+    /// Force the guest writes which happen when copying disk images for non-shared storage migration to be synchronously written to the destination
+    VirMigrateNonSharedSynchronousWrites = 262144,
+    /// Resume migration which failed in post-copy phase
+    VirMigratePostcopyResume = 524288,
+    /// Use zero-copy mechanism for migrating memory pages
+    VirMigrateZerocopy = 1048576,
+}
+
+#[napi]
+#[repr(u32)]
+pub enum VirConnectListAllDomainsFlags {
+    /// Active (running or paused) domains
+    VirConnectListDomainsActive = 1,
+    /// Inactive (shut off) domains
+    VirConnectListDomainsInactive = 2,
+    /// Domains with a persistent definition
+    VirConnectListDomainsPersistent = 4,
+    /// Transient domains
+    VirConnectListDomainsTransient = 8,
+    /// Running domains
+    VirConnectListDomainsRunning = 16,
+    /// Paused domains
+    VirConnectListDomainsPaused = 32,
+    /// Shut off domains
+    VirConnectListDomainsShutoff = 64,
+    /// Domains in any other state
+    VirConnectListDomainsOther = 128,
+    /// Domains with a managed save image
+    VirConnectListDomainsManagedsave = 256,
+    /// Domains without a managed save image
+    VirConnectListDomainsNoManagedsave = 512,
+    /// Domains marked for autostart
+    VirConnectListDomainsAutostart = 1024,
+    /// Domains not marked for autostart
+    VirConnectListDomainsNoAutostart = 2048,
+    /// Domains with snapshots
+    VirConnectListDomainsHasSnapshot = 4096,
+    /// Domains without snapshots
+    VirConnectListDomainsNoSnapshot = 8192,
+    /// Domains with checkpoints
+    VirConnectListDomainsHasCheckpoint = 16384,
+    /// Domains without checkpoints
+    VirConnectListDomainsNoCheckpoint = 32768,
+}
+
+#[napi]
+#[repr(u32)]
+pub enum VirConnectListAllStoragePoolsFlags {
+    /// Inactive pools
+    VirConnectListStoragePoolsInactive = 1,
+    /// Active pools
+    VirConnectListStoragePoolsActive = 2,
+    /// Pools with a persistent definition
+    VirConnectListStoragePoolsPersistent = 4,
+    /// Transient pools
+    VirConnectListStoragePoolsTransient = 8,
+    /// Pools marked for autostart
+    VirConnectListStoragePoolsAutostart = 16,
+    /// Pools not marked for autostart
+    VirConnectListStoragePoolsNoAutostart = 32,
+    /// Directory pools
+    VirConnectListStoragePoolsDir = 64,
+    /// Filesystem pools
+    VirConnectListStoragePoolsFs = 128,
+    /// Network filesystem pools
+    VirConnectListStoragePoolsNetfs = 256,
+    /// Logical (LVM) pools
+    VirConnectListStoragePoolsLogical = 512,
+    /// Disk pools
+    VirConnectListStoragePoolsDisk = 1024,
+    /// iSCSI pools
+    VirConnectListStoragePoolsIscsi = 2048,
+    /// SCSI pools
+    VirConnectListStoragePoolsScsi = 4096,
+    /// Multipath pools
+    VirConnectListStoragePoolsMpath = 8192,
+    /// RBD pools
+    VirConnectListStoragePoolsRbd = 16384,
+    /// Sheepdog pools
+    VirConnectListStoragePoolsSheepdog = 32768,
+    /// Gluster pools
+    VirConnectListStoragePoolsGluster = 65536,
+    /// ZFS pools
+    VirConnectListStoragePoolsZfs = 131072,
+    /// Virtuozzo storage pools
+    VirConnectListStoragePoolsVstorage = 262144,
+    /// iSCSI direct pools
+    VirConnectListStoragePoolsIscsiDirect = 524288,
+}
+
+#[napi]
+#[repr(u32)]
+pub enum VirDomainStatsTypes {
+    /// Domain state
+    VirDomainStatsState = 1,
+    /// Total CPU usage
+    VirDomainStatsCpuTotal = 2,
+    /// Balloon device
+    VirDomainStatsBalloon = 4,
+    /// Virtual CPUs
+    VirDomainStatsVcpu = 8,
+    /// Network interfaces
+    VirDomainStatsInterface = 16,
+    /// Block devices
+    VirDomainStatsBlock = 32,
+    /// Perf events
+    VirDomainStatsPerf = 64,
+    /// IOThreads
+    VirDomainStatsIothread = 128,
+    /// Memory bandwidth
+    VirDomainStatsMemory = 256,
+    /// Memory dirty rate
+    VirDomainStatsDirtyrate = 512,
+}
+
+#[napi]
+#[repr(u32)]
+pub enum VirConnectGetAllDomainStatsFlags {
+    /// Active domains
+    VirConnectGetAllDomainsStatsActive = 1,
+    /// Inactive domains
+    VirConnectGetAllDomainsStatsInactive = 2,
+    /// Persistent domains
+    VirConnectGetAllDomainsStatsPersistent = 4,
+    /// Transient domains
+    VirConnectGetAllDomainsStatsTransient = 8,
+    /// Running domains
+    VirConnectGetAllDomainsStatsRunning = 16,
+    /// Paused domains
+    VirConnectGetAllDomainsStatsPaused = 32,
+    /// Shut off domains
+    VirConnectGetAllDomainsStatsShutoff = 64,
+    /// Domains in any other state
+    VirConnectGetAllDomainsStatsOther = 128,
+    /// Report stats only if they can be obtained without waiting for a busy domain
+    VirConnectGetAllDomainsStatsNowait = 536870912,
+    /// Include stats for the backing chain of block devices
+    VirConnectGetAllDomainsStatsBacking = 1073741824,
+    /// Fail if a requested stats group is not supported
+    VirConnectGetAllDomainsStatsEnforceStats = 2147483648,
+}
+
+#[napi]
+#[repr(u32)]
+pub enum VirDomainDiskErrorCode {
+    /// No error
+    VirDomainDiskErrorNone = 0,
+    /// Unspecified I/O error
+    VirDomainDiskErrorUnspec = 1,
+    /// No space left on the device
+    VirDomainDiskErrorNoSpace = 2,
+}
+
+#[napi]
+#[repr(u32)]
+pub enum VirDomainDirtyRateCalcFlags {
+    /// Estimate the dirty rate by sampling guest pages
+    VirDomainDirtyRateModePageSampling = 0,
+    /// Use the dirty bitmap
+    VirDomainDirtyRateModeDirtyBitmap = 1,
+    /// Use the KVM dirty ring
+    VirDomainDirtyRateModeDirtyRing = 2,
+}
+
+#[napi]
+#[repr(u32)]
+pub enum VirDomainDirtyRateStatus {
+    /// The calculation has not been started
+    VirDomainDirtyRateUnstarted = 0,
+    /// The calculation is in progress
+    VirDomainDirtyRateMeasuring = 1,
+    /// The calculation finished and the rate is available
+    VirDomainDirtyRateMeasured = 2,
+}
+
+/// Flags for `attachDeviceFlags`, `detachDeviceFlags` and `updateDeviceFlags`.
+/// Hot-unplugging from a running domain and persisting the change needs
+/// `VirDomainDeviceModifyLive | VirDomainDeviceModifyConfig`.
+#[napi]
+#[repr(u32)]
+pub enum VirDomainDeviceModifyFlags {
+    /// Affect the current domain state
+    VirDomainDeviceModifyCurrent = 0,
+    /// Affect the running domain
+    VirDomainDeviceModifyLive = 1,
+    /// Affect the persistent configuration
+    VirDomainDeviceModifyConfig = 2,
+    /// Forcibly modify the device, e.g. eject a locked CD-ROM. Only use it for
+    /// a detach or update that is stuck, the guest is not asked first
+    VirDomainDeviceModifyForce = 4,
+}
+
+#[napi]
+#[repr(u32)]
+pub enum VirStorageVolWipeAlgorithm {
+    /// 1-pass, all zeroes
+    VirStorageVolWipeAlgZero = 0,
+    /// 4-pass NNSA Policy Letter NAP-14.1-C (XVI-8)
+    VirStorageVolWipeAlgNnsa = 1,
+    /// 4-pass DoD 5220.22-M section 8-306 procedure
+    VirStorageVolWipeAlgDod = 2,
+    /// 9-pass method recommended by the German Center of Security in Information Technologies
+    VirStorageVolWipeAlgBsi = 3,
+    /// The canonical 35-pass sequence
+    VirStorageVolWipeAlgGutmann = 4,
+    /// 7-pass method described by Bruce Schneier in "Applied Cryptography" (1996)
+    VirStorageVolWipeAlgSchneier = 5,
+    /// 7-pass random
+    VirStorageVolWipeAlgPfitzner7 = 6,
+    /// 33-pass random
+    VirStorageVolWipeAlgPfitzner33 = 7,
+    /// 1-pass random
+    VirStorageVolWipeAlgRandom = 8,
+    /// 1-pass, trim all data on the volume by using TRIM or DISCARD
+    VirStorageVolWipeAlgTrim = 9,
+}
+
+#[napi]
+#[repr(u32)]
+pub enum VirStorageVolCreateFlags {
+    /// Only allocate the metadata of the new volume (qcow2)
+    VirStorageVolCreatePreallocMetadata = 1,
+    /// Perform a btrfs lightweight copy when cloning a volume
+    VirStorageVolCreateReflink = 2,
+    /// Validate the XML document against the schema
+    VirStorageVolCreateValidate = 4,
+}
+
+#[napi]
+#[repr(u32)]
+pub enum VirStorageVolResizeFlags {
+    /// Force allocation of the new size
+    VirStorageVolResizeAllocate = 1,
+    /// The size is relative to the current capacity
+    VirStorageVolResizeDelta = 2,
+    /// Allow decreasing the capacity. Without it a smaller capacity is rejected;
+    /// with it data beyond the new size is lost
+    VirStorageVolResizeShrink = 4,
+}
+
+#[napi]
+#[repr(u32)]
+pub enum VirStorageVolDeleteFlags {
+    /// Delete metadata only (fast)
+    VirStorageVolDeleteNormal = 0,
+    /// Clear all data to zeros (slow)
+    VirStorageVolDeleteZeroed = 1,
+    /// Force removal of volume, even if in use
+    VirStorageVolDeleteWithSnapshots = 2,
+}
+
+#[napi]
+#[repr(u32)]
+pub enum VirStorageVolWipeFlags {
+    /// Default behavior, libvirt defines no wipe flags yet
+    VirStorageVolWipeDefault = 0,
+}
@@ -6,6 +6,7 @@ use napi;
 ///
 /// See <https://libvirt.org/html/libvirt-virterror.html#virErrorLevel>
 #[napi]
+#[repr(u32)]
 pub enum ErrorLevel {
 	/// No error.
 	None = 0,
@@ -19,6 +20,7 @@ pub enum ErrorLevel {
 ///
 /// See <https://libvirt.org/html/libvirt-virterror.html#virErrorDomain>
 #[napi]
+#[repr(u32)]
 pub enum ErrorDomain {
 	/// No error.
 	None = 0,
@@ -174,6 +176,7 @@ pub enum ErrorDomain {
 ///
 /// See <https://libvirt.org/html/libvirt-virterror.html#virErrorNumber>
 #[napi]
+#[repr(u32)]
 pub enum ErrorNumber {
 	/// No error.
 	Ok = 0,
@@ -403,6 +406,39 @@ pub enum ErrorNumber {
 	Last,
 }
 
+impl ErrorLevel {
+	/// Converts a raw `virErrorLevel`; unknown levels are reported as `Error`.
+	fn from_raw(level: u32) -> Self {
+		match level {
+			0 => ErrorLevel::None,
+			1 => ErrorLevel::Warning,
+			_ => ErrorLevel::Error,
+		}
+	}
+}
+
+impl ErrorDomain {
+	/// Converts a raw `virErrorDomain`; unknown domains map to `Last`.
+	fn from_raw(domain: u32) -> Self {
+		if domain >= ErrorDomain::Last as u32 {
+			return ErrorDomain::Last;
+		}
+		// The variants mirror virErrorDomain: contiguous from 0 up to `Last`.
+		unsafe { std::mem::transmute::<u32, ErrorDomain>(domain) }
+	}
+}
+
+impl ErrorNumber {
+	/// Converts a raw `virErrorNumber`; unknown codes map to `Last`.
+	fn from_raw(code: u32) -> Self {
+		if code >= ErrorNumber::Last as u32 {
+			return ErrorNumber::Last;
+		}
+		// The variants mirror virErrorNumber: contiguous from 0 up to `Last`.
+		unsafe { std::mem::transmute::<u32, ErrorNumber>(code) }
+	}
+}
+
 #[napi]
 pub struct Error {
 	pub code: u32,
@@ -423,4 +459,80 @@ impl Error {
 			level: err.level() as u32,
 		}
 	}
+
+	/// The error code as an ErrorNumber, `ErrorNumber.Last` if this build does not know it.
+	///
+	/// # Example (in JavaScript)
+	///
+	/// ```javascript
+	/// const err = Error.lastError();
+	/// switch (err.codeEnum()) {
+	///   case ErrorNumber.NoDomain:
+	///     console.log('domain is gone');
+	///     break;
+	///   case ErrorNumber.OperationInvalid:
+	///     console.log('domain is in the wrong state');
+	///     break;
+	/// }
+	/// ```
+	#[napi]
+	pub fn code_enum(&self) -> ErrorNumber {
+		ErrorNumber::from_raw(self.code)
+	}
+
+	/// The origin of the error as an ErrorDomain, `ErrorDomain.Last` if this build does not know it.
+	#[napi]
+	pub fn domain_enum(&self) -> ErrorDomain {
+		ErrorDomain::from_raw(self.domain)
+	}
+
+	/// The severity of the error as an ErrorLevel.
+	#[napi]
+	pub fn level_enum(&self) -> ErrorLevel {
+		ErrorLevel::from_raw(self.level)
+	}
+}
+
+impl Error {
+	/// Copies a libvirt error record, or returns `None` if `err` is null or
+	/// holds no error (`VIR_ERR_OK`).
+	pub(crate) unsafe fn from_raw(err: virt::sys::virErrorPtr) -> Option<Self> {
+		if err.is_null() || (*err).code == 0 {
+			return None;
+		}
+		let message = if (*err).message.is_null() {
+			String::new()
+		} else {
+			std::ffi::CStr::from_ptr((*err).message).to_string_lossy().into_owned()
+		};
+		Some(Error {
+			code: (*err).code as u32,
+			domain: (*err).domain as u32,
+			message,
+			level: (*err).level as u32,
+		})
+	}
+}
+
+/// Throws `err` as a JS `Error` and returns the error napi should propagate.
+///
+/// The thrown error's `message` is the libvirt message, i.e. the same string
+/// `Error.lastError().message` gives, and its `code`, `domain` and `level`
+/// properties hold the raw libvirt values (see ErrorNumber, ErrorDomain and
+/// ErrorLevel).
+pub(crate) fn throw_libvirt_error(env: &napi::Env, err: virt::error::Error) -> napi::Error {
+	let message = err.to_string();
+	let thrown = env
+		.create_error(napi::Error::new(napi::Status::GenericFailure, message.clone()))
+		.and_then(|mut js_err| {
+			js_err.set_named_property("code", err.code() as u32)?;
+			js_err.set_named_property("domain", err.domain() as u32)?;
+			js_err.set_named_property("level", err.level() as u32)?;
+			env.throw(js_err)
+		});
+	match thrown {
+		// napi does not throw again for an already pending exception.
+		Ok(_) => napi::Error::new(napi::Status::PendingException, message),
+		Err(e) => e,
+	}
 }
@@ -0,0 +1,279 @@
+use std::collections::HashMap;
+use std::ffi::CStr;
+use std::os::raw::{c_int, c_void};
+use std::sync::{Mutex, Once, OnceLock};
+use std::thread;
+
+use napi::threadsafe_function::{ErrorStrategy, ThreadsafeFunction, ThreadsafeFunctionCallMode};
+
+static EVENT_LOOP: Once = Once::new();
+
+/// Registers libvirt's default event loop implementation and starts the
+/// background thread that pumps it.
+///
+/// libvirt only delivers events on connections opened after an event loop
+/// has been registered, so this is called before every `Connection.open`.
+/// Only the first call has any effect.
+pub(crate) fn ensure_event_loop() {
+  EVENT_LOOP.call_once(|| {
+    if unsafe { virt::sys::virEventRegisterDefaultImpl() } < 0 {
+      return;
+    }
+    let _ = thread::Builder::new()
+      .name("libvirt-event-loop".to_string())
+      .spawn(|| loop {
+        unsafe {
+          virt::sys::virEventRunDefaultImpl();
+        }
+      });
+  });
+}
+
+/// A node device event delivered to `Connection.onNodeDeviceEvent` listeners.
+#[napi(object)]
+pub struct NodeDeviceEvent {
+  /// Name of the node device, e.g. `usb_1_2`.
+  pub device_name: String,
+  /// Which kind of event fired. See VirNodeDeviceEventID.
+  pub event_id: u32,
+  /// For lifecycle events, what happened. See VirNodeDeviceEventLifecycleType.
+  pub event: Option<i32>,
+  /// For lifecycle events, the driver specific detail of the event.
+  pub detail: Option<i32>,
+}
+
+pub(crate) type NodeDeviceEventCallback = ThreadsafeFunction<NodeDeviceEvent, ErrorStrategy::Fatal>;
+
+type NodeDeviceLifecycleCallback = unsafe extern "C" fn(
+  virt::sys::virConnectPtr,
+  virt::sys::virNodeDevicePtr,
+  c_int,
+  c_int,
+  *mut c_void,
+);
+type NodeDeviceGenericCallback =
+  unsafe extern "C" fn(virt::sys::virConnectPtr, virt::sys::virNodeDevicePtr, *mut c_void);
+
+unsafe fn node_device_name(dev: virt::sys::virNodeDevicePtr) -> String {
+  let name = virt::sys::virNodeDeviceGetName(dev);
+  if name.is_null() {
+    return String::new();
+  }
+  CStr::from_ptr(name).to_string_lossy().into_owned()
+}
+
+unsafe extern "C" fn node_device_lifecycle_callback(
+  _conn: virt::sys::virConnectPtr,
+  dev: virt::sys::virNodeDevicePtr,
+  event: c_int,
+  detail: c_int,
+  opaque: *mut c_void,
+) {
+  let callback = &*(opaque as *const NodeDeviceEventCallback);
+  callback.call(
+    NodeDeviceEvent {
+      device_name: node_device_name(dev),
+      event_id: virt::sys::VIR_NODE_DEVICE_EVENT_ID_LIFECYCLE as u32,
+      event: Some(event),
+      detail: Some(detail),
+    },
+    ThreadsafeFunctionCallMode::NonBlocking,
+  );
+}
+
+unsafe extern "C" fn node_device_update_callback(
+  _conn: virt::sys::virConnectPtr,
+  dev: virt::sys::virNodeDevicePtr,
+  opaque: *mut c_void,
+) {
+  let callback = &*(opaque as *const NodeDeviceEventCallback);
+  callback.call(
+    NodeDeviceEvent {
+      device_name: node_device_name(dev),
+      event_id: virt::sys::VIR_NODE_DEVICE_EVENT_ID_UPDATE as u32,
+      event: None,
+      detail: None,
+    },
+    ThreadsafeFunctionCallMode::NonBlocking,
+  );
+}
+
+unsafe extern "C" fn free_node_device_callback(opaque: *mut c_void) {
+  drop(Box::from_raw(opaque as *mut NodeDeviceEventCallback));
+}
+
+/// Callbacks of the live node device event registrations, keyed by connection
+/// and callback id, kept so deregistration can abort pending deliveries.
+fn node_device_event_callbacks() -> &'static Mutex<HashMap<(usize, i32), NodeDeviceEventCallback>> {
+  static CALLBACKS: OnceLock<Mutex<HashMap<(usize, i32), NodeDeviceEventCallback>>> = OnceLock::new();
+  CALLBACKS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Registers `callback` for node device events of type `event_id` on `conn`.
+///
+/// Returns the libvirt callback id, or `None` if the registration failed.
+pub(crate) fn register_node_device_event(
+  conn: virt::sys::virConnectPtr,
+  event_id: u32,
+  callback: NodeDeviceEventCallback,
+) -> Option<i32> {
+  // libvirt takes every node device callback through the generic signature
+  // and casts it back based on `event_id`.
+  let handler: NodeDeviceGenericCallback = if event_id
+    == virt::sys::VIR_NODE_DEVICE_EVENT_ID_LIFECYCLE as u32
+  {
+    let lifecycle: NodeDeviceLifecycleCallback = node_device_lifecycle_callback;
+    unsafe { std::mem::transmute::<NodeDeviceLifecycleCallback, NodeDeviceGenericCallback>(lifecycle) }
+  } else if event_id == virt::sys::VIR_NODE_DEVICE_EVENT_ID_UPDATE as u32 {
+    node_device_update_callback
+  } else {
+    return None;
+  };
+  let opaque = Box::into_raw(Box::new(callback.clone())) as *mut c_void;
+  let id = unsafe {
+    virt::sys::virConnectNodeDeviceEventRegisterAny(
+      conn,
+      std::ptr::null_mut(),
+      event_id as c_int,
+      Some(handler),
+      opaque,
+      Some(free_node_device_callback),
+    )
+  };
+  if id < 0 {
+    unsafe { free_node_device_callback(opaque) };
+    return None;
+  }
+  node_device_event_callbacks()
+    .lock()
+    .unwrap_or_else(|poisoned| poisoned.into_inner())
+    .insert((conn as usize, id), callback);
+  Some(id)
+}
+
+/// Removes a registration made with `register_node_device_event`.
+///
+/// Events libvirt already handed over but JS has not seen yet are dropped,
+/// so the callback never runs after this returns.
+pub(crate) fn deregister_node_device_event(conn: virt::sys::virConnectPtr, callback_id: i32) -> Option<i32> {
+  if unsafe { virt::sys::virConnectNodeDeviceEventDeregisterAny(conn, callback_id) } < 0 {
+    return None;
+  }
+  let callback = node_device_event_callbacks()
+    .lock()
+    .unwrap_or_else(|poisoned| poisoned.into_inner())
+    .remove(&(conn as usize, callback_id));
+  if let Some(callback) = callback {
+    let _ = callback.abort();
+  }
+  Some(0)
+}
+
+/// A domain lifecycle event delivered to `Connection.registerDomainEventLifecycle` listeners.
+#[napi(object)]
+pub struct DomainLifecycleEvent {
+  /// Name of the domain the event is about.
+  pub domain_name: String,
+  /// What happened. See VirDomainEventType.
+  pub event: i32,
+  /// Why it happened; the meaning depends on `event`.
+  pub detail: i32,
+}
+
+pub(crate) type DomainLifecycleCallback = ThreadsafeFunction<DomainLifecycleEvent, ErrorStrategy::Fatal>;
+
+type DomainLifecycleHandler = unsafe extern "C" fn(
+  virt::sys::virConnectPtr,
+  virt::sys::virDomainPtr,
+  c_int,
+  c_int,
+  *mut c_void,
+) -> c_int;
+type DomainGenericHandler =
+  unsafe extern "C" fn(virt::sys::virConnectPtr, virt::sys::virDomainPtr, *mut c_void);
+
+/// Callbacks of the live domain event registrations, keyed by connection and
+/// callback id, kept so deregistration can abort pending deliveries.
+fn domain_event_callbacks() -> &'static Mutex<HashMap<(usize, i32), DomainLifecycleCallback>> {
+  static CALLBACKS: OnceLock<Mutex<HashMap<(usize, i32), DomainLifecycleCallback>>> = OnceLock::new();
+  CALLBACKS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+unsafe extern "C" fn domain_lifecycle_callback(
+  _conn: virt::sys::virConnectPtr,
+  dom: virt::sys::virDomainPtr,
+  event: c_int,
+  detail: c_int,
+  opaque: *mut c_void,
+) -> c_int {
+  let callback = &*(opaque as *const DomainLifecycleCallback);
+  let name = virt::sys::virDomainGetName(dom);
+  callback.call(
+    DomainLifecycleEvent {
+      domain_name: if name.is_null() {
+        String::new()
+      } else {
+        CStr::from_ptr(name).to_string_lossy().into_owned()
+      },
+      event,
+      detail,
+    },
+    ThreadsafeFunctionCallMode::NonBlocking,
+  );
+  0
+}
+
+unsafe extern "C" fn free_domain_lifecycle_callback(opaque: *mut c_void) {
+  drop(Box::from_raw(opaque as *mut DomainLifecycleCallback));
+}
+
+/// Registers `callback` for lifecycle events of every domain on `conn`.
+///
+/// Returns the libvirt callback id, or `None` if the registration failed.
+pub(crate) fn register_domain_lifecycle_event(
+  conn: virt::sys::virConnectPtr,
+  callback: DomainLifecycleCallback,
+) -> Option<i32> {
+  let lifecycle: DomainLifecycleHandler = domain_lifecycle_callback;
+  // libvirt takes every domain callback through the generic signature and
+  // casts it back based on the event id.
+  let handler = unsafe { std::mem::transmute::<DomainLifecycleHandler, DomainGenericHandler>(lifecycle) };
+  let opaque = Box::into_raw(Box::new(callback.clone())) as *mut c_void;
+  let id = unsafe {
+    virt::sys::virConnectDomainEventRegisterAny(
+      conn,
+      std::ptr::null_mut(),
+      virt::sys::VIR_DOMAIN_EVENT_ID_LIFECYCLE as c_int,
+      Some(handler),
+      opaque,
+      Some(free_domain_lifecycle_callback),
+    )
+  };
+  if id < 0 {
+    unsafe { free_domain_lifecycle_callback(opaque) };
+    return None;
+  }
+  domain_event_callbacks()
+    .lock()
+    .unwrap_or_else(|poisoned| poisoned.into_inner())
+    .insert((conn as usize, id), callback);
+  Some(id)
+}
+
+/// Removes a registration made with `register_domain_lifecycle_event`.
+///
+/// Events libvirt already handed over but JS has not seen yet are dropped,
+/// so the callback never runs after this returns.
+pub(crate) fn deregister_domain_event(conn: virt::sys::virConnectPtr, callback_id: i32) -> Option<i32> {
+  if unsafe { virt::sys::virConnectDomainEventDeregisterAny(conn, callback_id) } < 0 {
+    return None;
+  }
+  let callback = domain_event_callbacks()
+    .lock()
+    .unwrap_or_else(|poisoned| poisoned.into_inner())
+    .remove(&(conn as usize, callback_id));
+  if let Some(callback) = callback {
+    let _ = callback.abort();
+  }
+  Some(0)
+}
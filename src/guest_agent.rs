@@ -1,6 +1,14 @@
 use napi::bindgen_prelude::*;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Size of the pieces `file_write` sends per `guest-file-write`. The agent
+/// caps the size of a single request, so large files are written in chunks.
+const FILE_WRITE_CHUNK_SIZE: usize = 256 * 1024;
 
 /// High-level wrapper for QEMU Guest Agent commands.
 /// Provides convenient methods for common guest operations.
@@ -39,6 +47,29 @@ pub struct ExecStatus {
     pub err_data: Option<String>,
 }
 
+/// An IP address of a guest network interface.
+#[napi]
+#[derive(Clone)]
+pub struct GuestIpAddress {
+    /// The address, e.g. `192.168.122.10` or `fe80::1`
+    pub ip_address: String,
+    /// Network prefix length
+    pub prefix: i64,
+    /// Either `ipv4` or `ipv6`
+    pub ip_address_type: String,
+}
+
+/// A network interface as seen by the guest OS.
+#[napi]
+pub struct GuestNetworkInterface {
+    /// Interface name inside the guest, e.g. `eth0` or `Ethernet 2`
+    pub name: String,
+    /// MAC address (if reported)
+    pub hardware_address: Option<String>,
+    /// Addresses assigned to the interface
+    pub ip_addresses: Vec<GuestIpAddress>,
+}
+
 /// Guest file information.
 #[napi]
 pub struct GuestFileInfo {
@@ -88,6 +119,7 @@ impl GuestAgent {
     /// * `cmd` - The command to execute.
     /// * `args` - Optional array of arguments.
     /// * `capture_output` - Whether to capture stdout/stderr.
+    /// * `input_data` - Optional data piped to the process stdin.
     ///
     /// # Returns
     ///
@@ -113,62 +145,75 @@ impl GuestAgent {
     ///   if (result) {
     ///     console.log('Output:', result.stdout);
     ///   }
+    ///
+    ///   // Feed data to the process stdin
+    ///   await agent.exec('/usr/bin/tee', ['/etc/app.conf'], true, Buffer.from('key=value\n'));
     /// }
     ///
     /// executeCommand();
     /// ```
     #[napi]
-    pub fn exec(&self, cmd: String, args: Option<Vec<String>>, capture_output: Option<bool>) -> Option<ExecResult> {
-        let capture = capture_output.unwrap_or(true);
-        
-        // Build the guest-exec command
-        let command = json!({
-            "execute": "guest-exec",
-            "arguments": {
-                "path": cmd,
-                "arg": args.unwrap_or_default(),
-                "capture-output": capture
-            }
-        });
+    pub fn exec(
+        &self,
+        cmd: String,
+        args: Option<Vec<String>>,
+        capture_output: Option<bool>,
+        input_data: Option<Buffer>,
+    ) -> Option<ExecResult> {
+        let pid = self.spawn(cmd, args, capture_output.unwrap_or(true), input_data)?;
 
-        // Execute via qemu_agent_command
-        match self.machine.qemu_agent_command(command.to_string(), 30, 0) {
-            Some(response_str) => {
-                // Parse the response
-                if let Ok(response) = serde_json::from_str::<Value>(&response_str) {
-                    if let Some(ret) = response.get("return") {
-                        let pid = ret.get("pid").and_then(|p| p.as_i64()).unwrap_or(-1) as i32;
-                        
-                        // Immediately check status to get output if available
-                        if let Some(status) = self.exec_status(pid) {
-                            return Some(ExecResult {
-                                pid,
-                                exitcode: status.exitcode,
-                                stdout: status.out_data.and_then(|data| {
-                                    base64::decode(&data).ok().and_then(|bytes| String::from_utf8(bytes).ok())
-                                }),
-                                stderr: status.err_data.and_then(|data| {
-                                    base64::decode(&data).ok().and_then(|bytes| String::from_utf8(bytes).ok())
-                                }),
-                                exited: status.exited,
-                            });
-                        }
-                        
-                        return Some(ExecResult {
-                            pid,
-                            exitcode: None,
-                            stdout: None,
-                            stderr: None,
-                            exited: false,
-                        });
-                    }
-                }
-                None
-            },
-            None => None
+        // Immediately check status to get output if available
+        match self.exec_status(pid) {
+            Some(status) => Some(ExecResult::from_status(pid, status)),
+            None => Some(ExecResult {
+                pid,
+                exitcode: None,
+                stdout: None,
+                stderr: None,
+                exited: false,
+            }),
         }
     }
 
+    /// Execute a command in the guest and wait for it to finish.
+    ///
+    /// `guest-exec-status` is polled with a growing delay (50ms up to 1s)
+    /// until the process exits or `timeout_ms` elapses.
+    ///
+    /// # Arguments
+    ///
+    /// * `cmd` - The command to execute.
+    /// * `args` - Optional array of arguments.
+    /// * `timeout_ms` - How long to wait for the process, in milliseconds.
+    ///
+    /// # Returns
+    ///
+    /// ExecResult with the decoded stdout/stderr, or null on error. On timeout
+    /// `exited` is false and the output is whatever the agent had buffered.
+    ///
+    /// # Example (in JavaScript)
+    ///
+    /// ```javascript
+    /// const { Machine, GuestAgent } = require('libvirt-node');
+    ///
+    /// async function runUpdate() {
+    ///   const machine = await Machine.lookupByName(conn, 'your-domain-name');
+    ///   const agent = new GuestAgent(machine);
+    ///   const result = agent.execAndWait('/usr/bin/apt-get', ['-y', 'upgrade'], 600000);
+    ///   if (result && result.exited) {
+    ///     console.log(`exit code ${result.exitcode}`, result.stdout);
+    ///   }
+    /// }
+    ///
+    /// runUpdate();
+    /// ```
+    #[napi]
+    pub fn exec_and_wait(&self, cmd: String, args: Option<Vec<String>>, timeout_ms: u32) -> Option<ExecResult> {
+        let pid = self.spawn(cmd, args, true, None)?;
+        let status = self.wait_for_exit(pid, Duration::from_millis(timeout_ms as u64))?;
+        Some(ExecResult::from_status(pid, status))
+    }
+
     /// Check the status of a running command.
     ///
     /// # Arguments
@@ -206,7 +251,7 @@ impl GuestAgent {
         }
     }
 
-    /// Read a file from the guest.
+    /// Read a text file from the guest.
     ///
     /// # Arguments
     ///
@@ -214,7 +259,9 @@ impl GuestAgent {
     ///
     /// # Returns
     ///
-    /// The file contents as a string, or null on error.
+    /// The file contents as a string (empty for an empty file), or null on
+    /// error or if the file is not valid UTF-8. Use `fileReadBytes` for
+    /// binary files.
     ///
     /// # Example (in JavaScript)
     ///
@@ -227,7 +274,7 @@ impl GuestAgent {
     ///   
     ///   // Read a configuration file
     ///   const content = await agent.fileRead('/etc/nginx/nginx.conf');
-    ///   if (content) {
+    ///   if (content !== null) {
     ///     console.log('File content:', content);
     ///   }
     /// }
@@ -236,81 +283,39 @@ impl GuestAgent {
     /// ```
     #[napi]
     pub fn file_read(&self, path: String) -> Option<String> {
-        // Open the file
-        let open_command = json!({
-            "execute": "guest-file-open",
-            "arguments": {
-                "path": path,
-                "mode": "r"
-            }
-        });
-
-        let handle = match self.machine.qemu_agent_command(open_command.to_string(), 5, 0) {
-            Some(response_str) => {
-                if let Ok(response) = serde_json::from_str::<Value>(&response_str) {
-                    response.get("return").and_then(|h| h.as_i64()).map(|h| h as i32)
-                } else {
-                    None
-                }
-            },
-            None => None
-        }?;
-
-        // Read the file content
-        let mut content = String::new();
-        let mut count = 4096; // Read in chunks
-        
-        loop {
-            let read_command = json!({
-                "execute": "guest-file-read",
-                "arguments": {
-                    "handle": handle,
-                    "count": count
-                }
-            });
-
-            match self.machine.qemu_agent_command(read_command.to_string(), 5, 0) {
-                Some(response_str) => {
-                    if let Ok(response) = serde_json::from_str::<Value>(&response_str) {
-                        if let Some(ret) = response.get("return") {
-                            let eof = ret.get("eof").and_then(|e| e.as_bool()).unwrap_or(true);
-                            
-                            if let Some(buf_b64) = ret.get("buf-b64").and_then(|b| b.as_str()) {
-                                if let Ok(decoded) = base64::decode(buf_b64) {
-                                    if let Ok(text) = String::from_utf8(decoded) {
-                                        content.push_str(&text);
-                                    }
-                                }
-                            }
-                            
-                            if eof {
-                                break;
-                            }
-                        } else {
-                            break;
-                        }
-                    } else {
-                        break;
-                    }
-                },
-                None => break
-            }
-        }
-
-        // Close the file
-        let close_command = json!({
-            "execute": "guest-file-close",
-            "arguments": {
-                "handle": handle
-            }
-        });
-        let _ = self.machine.qemu_agent_command(close_command.to_string(), 5, 0);
+        String::from_utf8(self.read_file(path)?).ok()
+    }
 
-        if !content.is_empty() {
-            Some(content)
-        } else {
-            None
-        }
+    /// Read a file from the guest as raw bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the file in the guest.
+    ///
+    /// # Returns
+    ///
+    /// The file contents (an empty buffer for an empty file), or null on error.
+    ///
+    /// # Example (in JavaScript)
+    ///
+    /// ```javascript
+    /// const fs = require('fs');
+    /// const { Machine, GuestAgent } = require('libvirt-node');
+    ///
+    /// async function fetchArtifact() {
+    ///   const machine = await Machine.lookupByName(conn, 'your-domain-name');
+    ///   const agent = new GuestAgent(machine);
+    ///   const data = agent.fileReadBytes('C:\\build\\output.zip');
+    ///   if (data !== null) {
+    ///     fs.writeFileSync('output.zip', data);
+    ///   }
+    /// }
+    ///
+    /// fetchArtifact();
+    /// ```
+    #[napi]
+    pub fn file_read_bytes(&self, path: String) -> Option<Buffer> {
+        self.read_file(path).map(Buffer::from)
     }
 
     /// Write content to a file in the guest.
@@ -377,28 +382,33 @@ impl GuestAgent {
         }
         let handle = handle.unwrap();
 
-        // Write the content
-        let content_b64 = base64::encode(content.as_bytes());
-        let write_command = json!({
-            "execute": "guest-file-write",
-            "arguments": {
-                "handle": handle,
-                "buf-b64": content_b64
-            }
-        });
-
-        let write_success = match self.machine.qemu_agent_command(write_command.to_string(), 5, 0) {
-            Some(response_str) => {
-                if let Ok(response) = serde_json::from_str::<Value>(&response_str) {
-                    response.get("return").is_some()
-                } else {
-                    false
+        // Write the content in chunks, the agent rejects oversized buffers
+        let mut write_success = true;
+        for chunk in content.as_bytes().chunks(FILE_WRITE_CHUNK_SIZE) {
+            let write_command = json!({
+                "execute": "guest-file-write",
+                "arguments": {
+                    "handle": handle,
+                    "buf-b64": base64::encode(chunk)
                 }
-            },
-            None => false
-        };
+            });
 
-        // Close the file
+            write_success = match self.machine.qemu_agent_command(write_command.to_string(), 30, 0) {
+                Some(response_str) => {
+                    if let Ok(response) = serde_json::from_str::<Value>(&response_str) {
+                        response.get("return").is_some()
+                    } else {
+                        false
+                    }
+                },
+                None => false
+            };
+            if !write_success {
+                break;
+            }
+        }
+
+        // Close the file, even after a failed write
         let close_command = json!({
             "execute": "guest-file-close",
             "arguments": {
@@ -424,6 +434,81 @@ impl GuestAgent {
         self.machine.qemu_agent_command(command.to_string(), 5, 0)
     }
 
+    /// Get the network interfaces of the guest and their addresses.
+    ///
+    /// # Arguments
+    ///
+    /// * `include_loopback` - Whether to include loopback interfaces (default: false).
+    ///
+    /// # Returns
+    ///
+    /// The guest interfaces, or null on error.
+    ///
+    /// # Example (in JavaScript)
+    ///
+    /// ```javascript
+    /// const { Machine, GuestAgent } = require('libvirt-node');
+    ///
+    /// async function guestIps() {
+    ///   const machine = await Machine.lookupByName(conn, 'your-domain-name');
+    ///   const agent = new GuestAgent(machine);
+    ///   for (const iface of agent.listInterfacesParsed() ?? []) {
+    ///     for (const addr of iface.ipAddresses) {
+    ///       console.log(iface.name, addr.ipAddressType, `${addr.ipAddress}/${addr.prefix}`);
+    ///     }
+    ///   }
+    /// }
+    ///
+    /// guestIps();
+    /// ```
+    #[napi]
+    pub fn list_interfaces_parsed(&self, include_loopback: Option<bool>) -> Option<Vec<GuestNetworkInterface>> {
+        let response = serde_json::from_str::<Value>(&self.get_network_interfaces()?).ok()?;
+        let interfaces = response.get("return")?.as_array()?;
+        let include_loopback = include_loopback.unwrap_or(false);
+
+        let mut result = Vec::new();
+        for iface in interfaces {
+            let name = match iface.get("name").and_then(|n| n.as_str()) {
+                Some(name) => name.to_string(),
+                None => continue,
+            };
+            let ip_addresses: Vec<GuestIpAddress> = iface
+                .get("ip-addresses")
+                .and_then(|a| a.as_array())
+                .map(|addresses| {
+                    addresses
+                        .iter()
+                        .filter_map(|addr| {
+                            Some(GuestIpAddress {
+                                ip_address: addr.get("ip-address")?.as_str()?.to_string(),
+                                prefix: addr.get("prefix").and_then(|p| p.as_i64()).unwrap_or(0),
+                                ip_address_type: addr
+                                    .get("ip-address-type")
+                                    .and_then(|t| t.as_str())
+                                    .unwrap_or_default()
+                                    .to_string(),
+                            })
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            if !include_loopback && is_loopback(&name, &ip_addresses) {
+                continue;
+            }
+            result.push(GuestNetworkInterface {
+                name,
+                hardware_address: iface
+                    .get("hardware-address")
+                    .and_then(|h| h.as_str())
+                    .map(String::from),
+                ip_addresses,
+            });
+        }
+        Some(result)
+    }
+
     /// Get the guest OS information.
     ///
     /// # Returns
@@ -438,6 +523,91 @@ impl GuestAgent {
         self.machine.qemu_agent_command(command.to_string(), 5, 0)
     }
 
+    /// Set the hostname of the guest OS.
+    ///
+    /// The guest OS is detected with `guest-get-osinfo` and the rename is run
+    /// through `guest-exec`:
+    /// * Windows guests run `Rename-Computer` through PowerShell. The new name
+    ///   only takes effect after the guest is rebooted.
+    /// * Other guests run `hostnamectl set-hostname`, which requires systemd.
+    ///
+    /// # Arguments
+    ///
+    /// * `hostname` - The new hostname: a single RFC 1123 label of ASCII
+    ///   letters, digits and `-`, not starting with `-`, at most 63
+    ///   characters (15 on Windows, the NetBIOS limit).
+    ///
+    /// # Returns
+    ///
+    /// true if the command exited with status 0, false if the name is not a
+    /// valid label, the agent is not reachable, the OS could not be detected
+    /// or the command failed.
+    ///
+    /// # Example (in JavaScript)
+    ///
+    /// ```javascript
+    /// const { Machine, GuestAgent } = require('libvirt-node');
+    ///
+    /// async function renameGuest() {
+    ///   const machine = await Machine.lookupByName(conn, 'your-domain-name');
+    ///   const agent = new GuestAgent(machine);
+    ///   if (!agent.setHostname('web-01')) {
+    ///     console.error('Failed to rename the guest');
+    ///   }
+    /// }
+    ///
+    /// renameGuest();
+    /// ```
+    #[napi]
+    pub fn set_hostname(&self, hostname: String) -> bool {
+        let os_info = match self.get_os_info() {
+            Some(response) => response,
+            None => return false,
+        };
+        let os_id = serde_json::from_str::<Value>(&os_info)
+            .ok()
+            .and_then(|response| {
+                response
+                    .get("return")
+                    .and_then(|ret| ret.get("id"))
+                    .and_then(|id| id.as_str())
+                    .map(String::from)
+            });
+        let os_id = match os_id {
+            Some(id) => id,
+            None => return false,
+        };
+
+        // The name ends up in a PowerShell command line or as a hostnamectl
+        // argument, so only plain hostname labels are passed on.
+        let windows = os_id == "mswindows";
+        let max_len = if windows { WINDOWS_HOSTNAME_MAX_LEN } else { HOSTNAME_LABEL_MAX_LEN };
+        if !is_valid_hostname(&hostname, max_len) {
+            return false;
+        }
+
+        let (cmd, args) = if windows {
+            (
+                "powershell.exe".to_string(),
+                vec![
+                    "-NoProfile".to_string(),
+                    "-Command".to_string(),
+                    format!("Rename-Computer -NewName '{}' -Force", hostname),
+                ],
+            )
+        } else {
+            (
+                "/usr/bin/hostnamectl".to_string(),
+                vec!["set-hostname".to_string(), hostname],
+            )
+        };
+
+        match self.exec_and_wait(cmd, Some(args), 30_000) {
+            Some(result) => result.exited && result.exitcode == Some(0),
+            None => false,
+        }
+    }
+
     /// Shutdown the guest OS.
     ///
     /// # Arguments
@@ -475,6 +645,54 @@ impl GuestAgent {
         self.machine.qemu_agent_command(command.to_string(), 5, 0).is_some()
     }
 
+    /// Check whether the guest agent is running and answering.
+    ///
+    /// Sends `guest-sync` with a random id and only accepts a reply echoing
+    /// that id, so a stale reply left over from an earlier command is not
+    /// mistaken for a live agent.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout_secs` - How long to wait for the reply, in seconds. Values below 1 are treated as 1.
+    ///
+    /// # Returns
+    ///
+    /// true if the agent replied in time, false otherwise.
+    ///
+    /// # Example (in JavaScript)
+    ///
+    /// ```javascript
+    /// const { Machine, GuestAgent } = require('libvirt-node');
+    ///
+    /// async function waitForAgent() {
+    ///   const machine = await Machine.lookupByName(conn, 'your-domain-name');
+    ///   const agent = new GuestAgent(machine);
+    ///   while (!agent.isResponsive(2)) {
+    ///     await new Promise((resolve) => setTimeout(resolve, 1000));
+    ///   }
+    /// }
+    ///
+    /// waitForAgent();
+    /// ```
+    #[napi]
+    pub fn is_responsive(&self, timeout_secs: i32) -> bool {
+        // RandomState is seeded randomly per instance, which is all the
+        // randomness a sync token needs.
+        let token = (RandomState::new().build_hasher().finish() >> 33) as i64;
+        let command = json!({
+            "execute": "guest-sync",
+            "arguments": {
+                "id": token
+            }
+        });
+
+        self.machine
+            .qemu_agent_command(command.to_string(), timeout_secs.max(1), 0)
+            .and_then(|response_str| serde_json::from_str::<Value>(&response_str).ok())
+            .and_then(|response| response.get("return").and_then(|id| id.as_i64()))
+            == Some(token)
+    }
+
     /// Set the guest time.
     ///
     /// # Arguments
@@ -568,46 +786,209 @@ impl GuestAgent {
     }
 }
 
-// Helper function for base64 encoding/decoding
+impl ExecResult {
+    fn from_status(pid: i32, status: ExecStatus) -> Self {
+        ExecResult {
+            pid,
+            exitcode: status.exitcode,
+            stdout: status.out_data.and_then(|data| {
+                base64::decode(&data).ok().and_then(|bytes| String::from_utf8(bytes).ok())
+            }),
+            stderr: status.err_data.and_then(|data| {
+                base64::decode(&data).ok().and_then(|bytes| String::from_utf8(bytes).ok())
+            }),
+            exited: status.exited,
+        }
+    }
+}
+
+impl GuestAgent {
+    /// Start `cmd` with `guest-exec` and return its pid.
+    fn spawn(
+        &self,
+        cmd: String,
+        args: Option<Vec<String>>,
+        capture: bool,
+        input_data: Option<Buffer>,
+    ) -> Option<i32> {
+        let mut command = json!({
+            "execute": "guest-exec",
+            "arguments": {
+                "path": cmd,
+                "arg": args.unwrap_or_default(),
+                "capture-output": capture
+            }
+        });
+
+        if let Some(input) = input_data {
+            command["arguments"]["input-data"] = Value::String(base64::encode(&input));
+        }
+
+        let response_str = self.machine.qemu_agent_command(command.to_string(), 30, 0)?;
+        let response = serde_json::from_str::<Value>(&response_str).ok()?;
+        let ret = response.get("return")?;
+        Some(ret.get("pid").and_then(|p| p.as_i64()).unwrap_or(-1) as i32)
+    }
+
+    /// Read the whole file at `path` through `guest-file-open`/`guest-file-read`.
+    ///
+    /// Returns `None` if the file cannot be opened or a read fails midway, so
+    /// a truncated file is never mistaken for the full content.
+    fn read_file(&self, path: String) -> Option<Vec<u8>> {
+        let open_command = json!({
+            "execute": "guest-file-open",
+            "arguments": {
+                "path": path,
+                "mode": "r"
+            }
+        });
+
+        let handle = self
+            .machine
+            .qemu_agent_command(open_command.to_string(), 5, 0)
+            .and_then(|response_str| serde_json::from_str::<Value>(&response_str).ok())
+            .and_then(|response| response.get("return").and_then(|h| h.as_i64()))?;
+
+        let mut content = Vec::new();
+        let mut complete = false;
+        loop {
+            let read_command = json!({
+                "execute": "guest-file-read",
+                "arguments": {
+                    "handle": handle,
+                    "count": 65536
+                }
+            });
+
+            let ret = match self
+                .machine
+                .qemu_agent_command(read_command.to_string(), 5, 0)
+                .and_then(|response_str| serde_json::from_str::<Value>(&response_str).ok())
+                .and_then(|response| response.get("return").cloned())
+            {
+                Some(ret) => ret,
+                None => break,
+            };
+
+            if let Some(buf_b64) = ret.get("buf-b64").and_then(|b| b.as_str()) {
+                match base64::decode(buf_b64) {
+                    Ok(decoded) => content.extend_from_slice(&decoded),
+                    Err(_) => break,
+                }
+            }
+
+            if ret.get("eof").and_then(|e| e.as_bool()).unwrap_or(true) {
+                complete = true;
+                break;
+            }
+        }
+
+        let close_command = json!({
+            "execute": "guest-file-close",
+            "arguments": {
+                "handle": handle
+            }
+        });
+        let _ = self.machine.qemu_agent_command(close_command.to_string(), 5, 0);
+
+        if complete {
+            Some(content)
+        } else {
+            None
+        }
+    }
+
+    /// Poll `guest-exec-status` until the process exits or `timeout` elapses.
+    ///
+    /// Returns the last status seen, which has `exited: false` on timeout.
+    fn wait_for_exit(&self, pid: i32, timeout: Duration) -> Option<ExecStatus> {
+        let deadline = Instant::now() + timeout;
+        let mut delay = Duration::from_millis(50);
+        loop {
+            let status = self.exec_status(pid)?;
+            if status.exited || Instant::now() >= deadline {
+                return Some(status);
+            }
+            thread::sleep(delay.min(deadline.saturating_duration_since(Instant::now())));
+            delay = (delay * 2).min(Duration::from_secs(1));
+        }
+    }
+}
+
+/// Whether a guest interface is a loopback: `lo` on Linux, the loopback
+/// pseudo-interface on Windows, or any interface with only loopback addresses.
+fn is_loopback(name: &str, addresses: &[GuestIpAddress]) -> bool {
+    name == "lo"
+        || name.starts_with("Loopback Pseudo-Interface")
+        || (!addresses.is_empty()
+            && addresses
+                .iter()
+                .all(|addr| addr.ip_address.starts_with("127.") || addr.ip_address == "::1"))
+}
+
+/// Longest hostname label allowed by RFC 1123.
+const HOSTNAME_LABEL_MAX_LEN: usize = 63;
+/// Longest computer name Windows accepts (NetBIOS limit).
+const WINDOWS_HOSTNAME_MAX_LEN: usize = 15;
+
+/// Whether `name` is a single RFC 1123 label of at most `max_len` characters:
+/// ASCII letters, digits and `-`, not starting with `-`.
+fn is_valid_hostname(name: &str, max_len: usize) -> bool {
+    !name.is_empty()
+        && name.len() <= max_len
+        && !name.starts_with('-')
+        && name.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-')
+}
+
+/// Standard base64 (RFC 4648, section 4) as used by the guest agent for
+/// file contents and exec input/output.
 mod base64 {
+    const TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
     pub fn encode(input: &[u8]) -> String {
-        // Simple base64 encoding implementation
-        const TABLE: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
-        let mut result = String::new();
-        
-        let mut i = 0;
-        while i < input.len() {
-            let b1 = input[i];
-            let b2 = if i + 1 < input.len() { input[i + 1] } else { 0 };
-            let b3 = if i + 2 < input.len() { input[i + 2] } else { 0 };
-            
+        let mut result = String::with_capacity((input.len() + 2) / 3 * 4);
+        for chunk in input.chunks(3) {
+            let b1 = chunk[0];
+            let b2 = chunk.get(1).copied().unwrap_or(0);
+            let b3 = chunk.get(2).copied().unwrap_or(0);
+
             result.push(TABLE[(b1 >> 2) as usize] as char);
             result.push(TABLE[(((b1 & 0x03) << 4) | (b2 >> 4)) as usize] as char);
-            result.push(if i + 1 < input.len() {
+            result.push(if chunk.len() > 1 {
                 TABLE[(((b2 & 0x0f) << 2) | (b3 >> 6)) as usize] as char
             } else {
                 '='
             });
-            result.push(if i + 2 < input.len() {
+            result.push(if chunk.len() > 2 {
                 TABLE[(b3 & 0x3f) as usize] as char
             } else {
                 '='
             });
-            
-            i += 3;
         }
-        
         result
     }
-    
+
+    /// Decodes `input`, ignoring ASCII whitespace (the guest agent may wrap
+    /// long output). Padding is optional, but when present it must only
+    /// appear at the end and complete the last quantum.
     pub fn decode(input: &str) -> Result<Vec<u8>, String> {
-        let input = input.trim_end_matches('=');
-        let mut result = Vec::new();
-        
+        let mut result = Vec::with_capacity(input.len() / 4 * 3);
         let mut buffer = 0u32;
         let mut bits_collected = 0;
-        
+        let mut symbols = 0usize;
+        let mut padding = 0usize;
+
         for c in input.chars() {
+            if c.is_ascii_whitespace() {
+                continue;
+            }
+            if c == '=' {
+                padding += 1;
+                continue;
+            }
+            if padding > 0 {
+                return Err("Invalid base64: data after padding".to_string());
+            }
             let value = match c {
                 'A'..='Z' => c as u32 - 'A' as u32,
                 'a'..='z' => c as u32 - 'a' as u32 + 26,
@@ -616,17 +997,94 @@ mod base64 {
                 '/' => 63,
                 _ => return Err(format!("Invalid base64 character: {}", c)),
             };
-            
+            symbols += 1;
+
             buffer = (buffer << 6) | value;
             bits_collected += 6;
-            
+
             if bits_collected >= 8 {
                 bits_collected -= 8;
                 result.push((buffer >> bits_collected) as u8);
                 buffer &= (1 << bits_collected) - 1;
             }
         }
-        
+
+        if symbols % 4 == 1 {
+            return Err("Invalid base64: truncated input".to_string());
+        }
+        if padding > 0 && (padding > 2 || (symbols + padding) % 4 != 0) {
+            return Err("Invalid base64: wrong padding".to_string());
+        }
         Ok(result)
     }
-}
\ No newline at end of file
+
+    #[cfg(test)]
+    mod tests {
+        use super::{decode, encode};
+
+        #[test]
+        fn round_trips_lengths_0_to_5() {
+            let data = b"foobar";
+            for len in 0..=5 {
+                let encoded = encode(&data[..len]);
+                assert_eq!(encoded.len() % 4, 0);
+                assert_eq!(decode(&encoded).unwrap(), &data[..len]);
+            }
+        }
+
+        #[test]
+        fn decodes_with_and_without_padding() {
+            assert_eq!(decode("").unwrap(), b"");
+            assert_eq!(decode("Zg==").unwrap(), b"f");
+            assert_eq!(decode("Zg").unwrap(), b"f");
+            assert_eq!(decode("Zm8").unwrap(), b"fo");
+            assert_eq!(decode("Zm9v").unwrap(), b"foo");
+        }
+
+        #[test]
+        fn skips_embedded_newlines() {
+            assert_eq!(decode("Zm9v\nYmFy\n").unwrap(), b"foobar");
+        }
+
+        #[test]
+        fn rejects_malformed_input() {
+            assert!(decode("Z").is_err());
+            assert!(decode("Zg=a").is_err());
+            assert!(decode("Zg===").is_err());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_valid_hostname, HOSTNAME_LABEL_MAX_LEN, WINDOWS_HOSTNAME_MAX_LEN};
+
+    #[test]
+    fn accepts_plain_labels() {
+        assert!(is_valid_hostname("web-01", HOSTNAME_LABEL_MAX_LEN));
+        assert!(is_valid_hostname(&"a".repeat(63), HOSTNAME_LABEL_MAX_LEN));
+        assert!(is_valid_hostname(&"a".repeat(15), WINDOWS_HOSTNAME_MAX_LEN));
+    }
+
+    #[test]
+    fn rejects_unsafe_or_invalid_names() {
+        for name in [
+            "",
+            "-rf",
+            "--static",
+            "web'; Remove-Item C:\\ -Recurse; '",
+            "web\u{2019}; calc; \u{2018}",
+            "web\u{201A}",
+            "web\u{201B}",
+            "web 01",
+            "web.example.com",
+            "web_01",
+            "h\u{f6}st",
+            "web\n01",
+        ] {
+            assert!(!is_valid_hostname(name, HOSTNAME_LABEL_MAX_LEN), "{:?}", name);
+        }
+        assert!(!is_valid_hostname(&"a".repeat(64), HOSTNAME_LABEL_MAX_LEN));
+        assert!(!is_valid_hostname(&"a".repeat(16), WINDOWS_HOSTNAME_MAX_LEN));
+    }
+}
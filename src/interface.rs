@@ -1,11 +1,14 @@
 use napi;
 use virt;
 
+use crate::connection::Connection;
+
 #[napi]
 pub struct Interface {
 	interface: virt::interface::Interface
 }
 
+#[napi]
 impl Interface {
 	pub fn get(&self) -> &virt::interface::Interface {
 		&self.interface
@@ -14,4 +17,87 @@ impl Interface {
 	pub fn from_interface(int: virt::interface::Interface) -> Self {
 		Self { interface: int }
 	}
-}
\ No newline at end of file
+
+	#[napi]
+	pub fn lookup_by_name(conn: &Connection, name: String) -> Option<Interface> {
+		match virt::interface::Interface::lookup_by_name(conn.get_connection(), &name) {
+			Ok(int) => Some(Interface::from_interface(int)),
+			Err(_) => None,
+		}
+	}
+
+	#[napi]
+	pub fn lookup_by_mac_string(conn: &Connection, mac: String) -> Option<Interface> {
+		match virt::interface::Interface::lookup_by_mac_string(conn.get_connection(), &mac) {
+			Ok(int) => Some(Interface::from_interface(int)),
+			Err(_) => None,
+		}
+	}
+
+	#[napi]
+	pub fn define_xml(conn: &Connection, xml: String) -> Option<Interface> {
+		match virt::interface::Interface::define_xml(conn.get_connection(), &xml, 0) {
+			Ok(int) => Some(Interface::from_interface(int)),
+			Err(_) => None,
+		}
+	}
+
+	#[napi]
+	pub fn get_name(&self) -> Option<String> {
+		match self.interface.get_name() {
+			Ok(name) => Some(name),
+			Err(_) => None,
+		}
+	}
+
+	/// Returns the MAC address of the interface, or `null` if it has none
+	/// (e.g. a bridge without an address of its own).
+	#[napi]
+	pub fn get_mac_string(&self) -> Option<String> {
+		match self.interface.get_mac_string() {
+			Ok(mac) if !mac.is_empty() => Some(mac),
+			_ => None,
+		}
+	}
+
+	#[napi]
+	pub fn get_xml_desc(&self, flags: Option<u32>) -> Option<String> {
+		match self.interface.get_xml_desc(flags.unwrap_or(0)) {
+			Ok(xml) => Some(xml),
+			Err(_) => None,
+		}
+	}
+
+	#[napi]
+	pub fn create(&self, flags: u32) -> Option<u32> {
+		match self.interface.create(flags) {
+			Ok(_) => Some(0),
+			Err(_) => None,
+		}
+	}
+
+	/// Brings the interface down. Returns `null` if it is not active.
+	#[napi]
+	pub fn destroy(&self, flags: u32) -> Option<u32> {
+		match self.interface.destroy(flags) {
+			Ok(_) => Some(0),
+			Err(_) => None,
+		}
+	}
+
+	#[napi]
+	pub fn undefine(&self) -> Option<u32> {
+		match self.interface.undefine() {
+			Ok(_) => Some(0),
+			Err(_) => None,
+		}
+	}
+
+	#[napi]
+	pub fn is_active(&self) -> Option<bool> {
+		match self.interface.is_active() {
+			Ok(active) => Some(active),
+			Err(_) => None,
+		}
+	}
+}
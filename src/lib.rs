@@ -4,6 +4,7 @@ extern crate napi_derive;
 mod connection;
 mod machine;
 mod network;
+mod network_port;
 mod interface;
 mod node_device;
 mod secret;
@@ -12,7 +13,13 @@ mod storage_vol;
 mod nw_filter;
 mod node_info;
 mod domain_stats_record;
+mod domain_diff;
 mod enums;
 mod error;
+mod events;
 mod snapshot;
+mod stream;
+mod tasks;
 mod guest_agent;
+mod typed_params;
+mod xml;
@@ -1,3 +1,6 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
 use napi::{
   bindgen_prelude::BigInt, JsObject,
 };
@@ -44,12 +47,22 @@ pub struct Time {
 /// Check https://libvirt.org/html/libvirt-libvirt-domain.html#virDomainState
 #[napi]
 pub struct StateResult {
-  /// The result of the state. 0 if success, 1 if failure.
+  /// The state of the domain. See VirDomainState.
   pub result: u32,
-  /// The reason of the state. It's a flag, Check libvirt documentation for more info.
+  /// Why the domain is in that state; the meaning depends on the state.
+  /// `getStateString` renders it as text.
   pub reason: i32,
 }
 
+/// The live and persistent definitions of a domain.
+#[napi]
+pub struct DomainDefinitions {
+  /// XML of the running domain. `null` if the domain is not running.
+  pub live: Option<String>,
+  /// XML the domain will use on its next boot.
+  pub config: String,
+}
+
 /// Represents the block info.
 #[napi]
 pub struct BlockInfo {
@@ -65,6 +78,7 @@ pub struct BlockInfo {
 }
 
 #[napi]
+#[derive(Clone)]
 pub struct InterfaceStats {
   pub rx_bytes: i64,
   pub rx_packets: i64,
@@ -76,6 +90,169 @@ pub struct InterfaceStats {
   pub tx_drop: i64,
 }
 
+impl From<virt::domain::InterfaceStats> for InterfaceStats {
+  fn from(stats: virt::domain::InterfaceStats) -> Self {
+    InterfaceStats {
+      rx_bytes: stats.rx_bytes,
+      rx_packets: stats.rx_packets,
+      rx_errs: stats.rx_errs,
+      rx_drop: stats.rx_drop,
+      tx_bytes: stats.tx_bytes,
+      tx_packets: stats.tx_packets,
+      tx_errs: stats.tx_errs,
+      tx_drop: stats.tx_drop,
+    }
+  }
+}
+
+/// I/O counters of one block device of a domain.
+///
+/// Counters are cumulative since the domain was started and may be reset
+/// when the domain is migrated. Counters the hypervisor does not track are `-1`.
+#[napi]
+pub struct BlockStats {
+  /// Number of read requests.
+  pub rd_req: i64,
+  /// Number of bytes read.
+  pub rd_bytes: i64,
+  /// Number of write requests.
+  pub wr_req: i64,
+  /// Number of bytes written.
+  pub wr_bytes: i64,
+  /// Number of errors; the meaning is hypervisor specific.
+  pub errs: i64,
+}
+
+/// CPU time accounting of a domain, either in total or on one host CPU.
+///
+/// All times are in nanoseconds.
+#[napi]
+pub struct CpuStat {
+  /// Host CPU the entry is about, or `null` for the aggregate entry.
+  pub cpu: Option<u32>,
+  /// CPU time consumed by the domain, including hypervisor overhead.
+  pub cpu_time: BigInt,
+  /// Time spent in user mode. Only reported in the aggregate entry.
+  pub user_time: Option<BigInt>,
+  /// Time spent in kernel mode. Only reported in the aggregate entry.
+  pub system_time: Option<BigInt>,
+  /// CPU time consumed by the vCPUs only. Only reported per host CPU.
+  pub vcpu_time: Option<BigInt>,
+}
+
+/// Run state and placement of one vCPU of a running domain.
+#[napi]
+pub struct VcpuInfo {
+  /// Virtual CPU number.
+  pub number: u32,
+  /// Run state. See VirVcpuState.
+  pub state: i32,
+  /// CPU time used, in nanoseconds.
+  pub cpu_time: BigInt,
+  /// Host CPU the vCPU is currently running on, or `-1` if offline.
+  pub cpu: i32,
+  /// Host CPUs the vCPU may run on, indexed by host CPU number.
+  pub affinity: Vec<bool>,
+}
+
+/// An I/O thread of a domain and the host CPUs it is pinned to.
+#[napi]
+pub struct IOThreadInfo {
+  /// ID of the I/O thread, as used in the domain XML.
+  pub iothread_id: u32,
+  /// Host CPUs the thread may run on, indexed by host CPU number.
+  pub cpumap: Vec<bool>,
+}
+
+/// Balloon and memory usage of a domain, in KiB. Fields the hypervisor or the
+/// guest balloon driver does not report are `null`.
+#[napi]
+pub struct BalloonInfo {
+  /// Current balloon size, i.e. the memory the guest currently has.
+  pub actual: Option<BigInt>,
+  /// Resident set size of the domain process on the host.
+  pub rss: Option<BigInt>,
+  /// Memory usable by the guest OS, as seen by the guest.
+  pub available: Option<BigInt>,
+  /// Memory that can be reclaimed by the guest without swapping.
+  pub usable: Option<BigInt>,
+  /// Memory left completely unused by the guest.
+  pub unused: Option<BigInt>,
+}
+
+/// Progress of the job (migration, save, dump, ...) running on a domain.
+/// Times are in milliseconds and sizes in bytes.
+#[napi]
+pub struct DomainJobInfo {
+  /// Kind of job. See VirDomainJobType.
+  #[napi(js_name = "type")]
+  pub kind: i32,
+  /// Time since the job started.
+  pub time_elapsed: BigInt,
+  /// Estimated time until the job completes.
+  pub time_remaining: BigInt,
+  /// Total data to transfer (memory plus files).
+  pub data_total: BigInt,
+  pub data_processed: BigInt,
+  pub data_remaining: BigInt,
+  /// Guest memory to transfer.
+  pub mem_total: BigInt,
+  pub mem_processed: BigInt,
+  pub mem_remaining: BigInt,
+}
+
+/// Progress of the block job running on a disk.
+#[napi]
+pub struct BlockJobInfo {
+  /// Kind of job. See VirDomainBlockJobType.
+  #[napi(js_name = "type")]
+  pub kind: i32,
+  /// Bandwidth limit in MiB/s, `0` if unlimited.
+  pub bandwidth: BigInt,
+  /// Progress so far, in units of `end`.
+  pub cur: BigInt,
+  /// Value `cur` reaches once the job is done.
+  pub end: BigInt,
+}
+
+/// An image of a guest display.
+#[napi(object)]
+pub struct Screenshot {
+  /// Image format chosen by the hypervisor, e.g. `image/png` or `image/x-portable-pixmap`.
+  pub mime: String,
+  /// The encoded image.
+  pub data: Buffer,
+}
+
+/// The security label the domain process runs with.
+#[napi]
+pub struct SecurityLabel {
+  /// The label, e.g. `system_u:system_r:svirt_t:s0:c12,c345`; empty if unconfined.
+  pub label: String,
+  /// Whether the security policy is enforced for the domain.
+  pub enforcing: bool,
+}
+
+/// State of the connection between libvirt and the hypervisor process of a domain.
+#[napi]
+pub struct DomainControlInfo {
+  /// See VirDomainControlState.
+  pub state: u32,
+  /// Reason for an error state, see virDomainControlErrorReason.
+  pub details: u32,
+  /// Milliseconds spent in the current state, for the occupied and error states.
+  pub state_time: BigInt,
+}
+
+/// An I/O error recorded on a disk of a domain.
+#[napi]
+pub struct DiskError {
+  /// Target of the disk, e.g. `vda`.
+  pub disk: String,
+  /// See VirDomainDiskErrorCode.
+  pub error: u32,
+}
+
 #[napi]
 pub struct MemoryStat {
   pub tag: u32,
@@ -132,6 +309,236 @@ impl FromNapiValue for NUMAParameters {
   }
 }
 
+#[napi]
+pub struct BlkioParameters {
+    /// Relative I/O weight of the domain, in the range [100, 1000].
+    pub weight: Option<u32>,
+    /// Per device weights as `/path/to/device,weight,/path/to/other,weight`.
+    pub device_weights: Option<String>,
+}
+
+impl FromNapiValue for BlkioParameters {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let obj = JsObject::from_napi_value(env, napi_val)?;
+    let weight: Option<u32> = obj.get("weight")?;
+    let device_weights: Option<String> = obj.get("deviceWeights")?;
+    Ok(Self { weight, device_weights })
+  }
+}
+
+/// I/O limits of one disk of a domain. `0` means unlimited; fields left out
+/// are not changed.
+#[napi]
+pub struct BlockIoTune {
+    /// Total throughput limit in bytes per second.
+    pub total_bytes_sec: Option<BigInt>,
+    /// Read throughput limit in bytes per second.
+    pub read_bytes_sec: Option<BigInt>,
+    /// Write throughput limit in bytes per second.
+    pub write_bytes_sec: Option<BigInt>,
+    /// Total I/O operations per second.
+    pub total_iops_sec: Option<BigInt>,
+    /// Read I/O operations per second.
+    pub read_iops_sec: Option<BigInt>,
+    /// Write I/O operations per second.
+    pub write_iops_sec: Option<BigInt>,
+}
+
+impl FromNapiValue for BlockIoTune {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let obj = JsObject::from_napi_value(env, napi_val)?;
+    let total_bytes_sec: Option<BigInt> = obj.get("totalBytesSec")?;
+    let read_bytes_sec: Option<BigInt> = obj.get("readBytesSec")?;
+    let write_bytes_sec: Option<BigInt> = obj.get("writeBytesSec")?;
+    let total_iops_sec: Option<BigInt> = obj.get("totalIopsSec")?;
+    let read_iops_sec: Option<BigInt> = obj.get("readIopsSec")?;
+    let write_iops_sec: Option<BigInt> = obj.get("writeIopsSec")?;
+
+    Ok(Self {
+      total_bytes_sec,
+      read_bytes_sec,
+      write_bytes_sec,
+      total_iops_sec,
+      read_iops_sec,
+      write_iops_sec,
+    })
+  }
+}
+
+/// Field names of the `BlockIoTune` limits in libvirt's typed parameter list.
+const BLOCK_IO_TUNE_FIELDS: [&str; 6] = [
+  "total_bytes_sec",
+  "read_bytes_sec",
+  "write_bytes_sec",
+  "total_iops_sec",
+  "read_iops_sec",
+  "write_iops_sec",
+];
+
+/// Hardware and software perf counters of a domain. `true` enables an event,
+/// `false` disables it and fields left out are not changed.
+#[napi]
+pub struct PerfEvents {
+    /// Cache monitoring technology: L3 cache usage of the domain's processes.
+    pub cmt: Option<bool>,
+    /// Total system memory bandwidth used by the domain's processes.
+    pub mbmt: Option<bool>,
+    /// Local memory bandwidth used by the domain's processes.
+    pub mbml: Option<bool>,
+    /// CPU cycles.
+    pub cpu_cycles: Option<bool>,
+    /// Retired instructions.
+    pub instructions: Option<bool>,
+    /// Cache accesses.
+    pub cache_references: Option<bool>,
+    /// Cache misses.
+    pub cache_misses: Option<bool>,
+    /// Retired branch instructions.
+    pub branch_instructions: Option<bool>,
+    /// Mispredicted branch instructions.
+    pub branch_misses: Option<bool>,
+    /// Bus cycles.
+    pub bus_cycles: Option<bool>,
+    /// Stalled CPU cycles in the instruction fetch frontend.
+    pub stalled_cycles_frontend: Option<bool>,
+    /// Stalled CPU cycles in the execution backend.
+    pub stalled_cycles_backend: Option<bool>,
+    /// CPU cycles not affected by frequency scaling.
+    pub ref_cpu_cycles: Option<bool>,
+    /// CPU clock time, as measured by a high resolution timer.
+    pub cpu_clock: Option<bool>,
+    /// Task clock time, counted only while the domain's processes run.
+    pub task_clock: Option<bool>,
+    /// Page faults.
+    pub page_faults: Option<bool>,
+    /// Context switches.
+    pub context_switches: Option<bool>,
+    /// Migrations of the domain's processes between host CPUs.
+    pub cpu_migrations: Option<bool>,
+    /// Minor page faults.
+    pub page_faults_min: Option<bool>,
+    /// Major page faults.
+    pub page_faults_maj: Option<bool>,
+    /// Alignment faults.
+    pub alignment_faults: Option<bool>,
+    /// Emulation faults.
+    pub emulation_faults: Option<bool>,
+}
+
+impl FromNapiValue for PerfEvents {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let obj = JsObject::from_napi_value(env, napi_val)?;
+    let cmt: Option<bool> = obj.get("cmt")?;
+    let mbmt: Option<bool> = obj.get("mbmt")?;
+    let mbml: Option<bool> = obj.get("mbml")?;
+    let cpu_cycles: Option<bool> = obj.get("cpuCycles")?;
+    let instructions: Option<bool> = obj.get("instructions")?;
+    let cache_references: Option<bool> = obj.get("cacheReferences")?;
+    let cache_misses: Option<bool> = obj.get("cacheMisses")?;
+    let branch_instructions: Option<bool> = obj.get("branchInstructions")?;
+    let branch_misses: Option<bool> = obj.get("branchMisses")?;
+    let bus_cycles: Option<bool> = obj.get("busCycles")?;
+    let stalled_cycles_frontend: Option<bool> = obj.get("stalledCyclesFrontend")?;
+    let stalled_cycles_backend: Option<bool> = obj.get("stalledCyclesBackend")?;
+    let ref_cpu_cycles: Option<bool> = obj.get("refCpuCycles")?;
+    let cpu_clock: Option<bool> = obj.get("cpuClock")?;
+    let task_clock: Option<bool> = obj.get("taskClock")?;
+    let page_faults: Option<bool> = obj.get("pageFaults")?;
+    let context_switches: Option<bool> = obj.get("contextSwitches")?;
+    let cpu_migrations: Option<bool> = obj.get("cpuMigrations")?;
+    let page_faults_min: Option<bool> = obj.get("pageFaultsMin")?;
+    let page_faults_maj: Option<bool> = obj.get("pageFaultsMaj")?;
+    let alignment_faults: Option<bool> = obj.get("alignmentFaults")?;
+    let emulation_faults: Option<bool> = obj.get("emulationFaults")?;
+
+    Ok(Self {
+      cmt,
+      mbmt,
+      mbml,
+      cpu_cycles,
+      instructions,
+      cache_references,
+      cache_misses,
+      branch_instructions,
+      branch_misses,
+      bus_cycles,
+      stalled_cycles_frontend,
+      stalled_cycles_backend,
+      ref_cpu_cycles,
+      cpu_clock,
+      task_clock,
+      page_faults,
+      context_switches,
+      cpu_migrations,
+      page_faults_min,
+      page_faults_maj,
+      alignment_faults,
+      emulation_faults,
+    })
+  }
+}
+
+/// Names of the `PerfEvents` events in libvirt's typed parameter list.
+const PERF_EVENT_FIELDS: [&str; 22] = [
+  "cmt",
+  "mbmt",
+  "mbml",
+  "cpu_cycles",
+  "instructions",
+  "cache_references",
+  "cache_misses",
+  "branch_instructions",
+  "branch_misses",
+  "bus_cycles",
+  "stalled_cycles_frontend",
+  "stalled_cycles_backend",
+  "ref_cpu_cycles",
+  "cpu_clock",
+  "task_clock",
+  "page_faults",
+  "context_switches",
+  "cpu_migrations",
+  "page_faults_min",
+  "page_faults_maj",
+  "alignment_faults",
+  "emulation_faults",
+];
+
+/// CPU scheduler tuning of a domain. Periods are in microseconds; a quota of
+/// `-1` means unlimited. Fields left out are not changed.
+#[napi]
+pub struct SchedulerParameters {
+    /// Relative CPU weight of the domain against other domains.
+    pub cpu_shares: Option<BigInt>,
+    /// Enforcement period of `vcpuQuota`.
+    pub vcpu_period: Option<BigInt>,
+    /// Maximum run time of each vCPU per `vcpuPeriod`.
+    pub vcpu_quota: Option<BigInt>,
+    /// Enforcement period of `emulatorQuota`.
+    pub emulator_period: Option<BigInt>,
+    /// Maximum run time of the emulator threads per `emulatorPeriod`.
+    pub emulator_quota: Option<BigInt>,
+}
+
+impl FromNapiValue for SchedulerParameters {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let obj = JsObject::from_napi_value(env, napi_val)?;
+    let cpu_shares: Option<BigInt> = obj.get("cpuShares")?;
+    let vcpu_period: Option<BigInt> = obj.get("vcpuPeriod")?;
+    let vcpu_quota: Option<BigInt> = obj.get("vcpuQuota")?;
+    let emulator_period: Option<BigInt> = obj.get("emulatorPeriod")?;
+    let emulator_quota: Option<BigInt> = obj.get("emulatorQuota")?;
+
+    Ok(Self {
+      cpu_shares,
+      vcpu_period,
+      vcpu_quota,
+      emulator_period,
+      emulator_quota,
+    })
+  }
+}
+
 #[napi]
 impl Machine {
   pub fn from_domain(domain: Domain, con: &Connection) -> Self {
@@ -141,6 +548,12 @@ impl Machine {
     }
   }
 
+  /// Builds a machine around a connection that already shares the parent
+  /// handle (see `Connection::share`), avoiding a reconnect per domain.
+  pub fn from_shared_domain(domain: Domain, con: Connection) -> Self {
+    Self { domain, con }
+  }
+
   /// Looks up a domain by its name.
   ///
   /// # Arguments
@@ -287,15 +700,14 @@ impl Machine {
   /// # Example (in JavaScript)
   ///
   /// ```javascript
-  /// const { Connection, Machine } = require('your-node-package');
-  /// const VIR_DOMAIN_RUNNING = 1;
+  /// const { Connection, Machine, VirDomainState } = require('your-node-package');
   ///
   /// async function getDomainState() {
   ///   const conn = await Connection.open('qemu:///system');
   ///   const machine = await Machine.lookupByName(conn, 'your-domain-name');
   ///   const state = machine.getState();
   ///   if (state) {
-  ///     if (state.result === VIR_DOMAIN_RUNNING) {
+  ///     if (state.result === VirDomainState.VirDomainRunning) {
   ///       console.log('Domain is running');
   ///     } else {
   ///       console.log('Domain is not running');
@@ -319,6 +731,26 @@ impl Machine {
     }
   }
 
+  /// Get the state of the domain and the reason for it as text, in the
+  /// format used by `virsh domstate --reason`.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `string` - e.g. `running (booted)`, `paused (user)` or `shut off (destroyed)`.
+  /// * `null` - If there is an error during the lookup.
+  ///
+  /// # Example (in JavaScript)
+  ///
+  /// ```javascript
+  /// console.log(`${machine.getName()}: ${machine.getStateString()}`);
+  /// ```
+  #[napi]
+  pub fn get_state_string(&self) -> Option<String> {
+    let (state, reason) = self.domain.get_state().ok()?;
+    Some(format!("{} ({})", state_label(state), state_reason_label(state, reason)))
+  }
+
   /// Get the name of the domain.
   ///
   /// # Returns
@@ -416,8 +848,8 @@ impl Machine {
   /// getDomainHostname();
   /// ```
   #[napi]
-  pub fn get_hostname(&self, flags: u32) -> Option<String> {
-    let hostname_result = self.domain.get_hostname(flags);
+  pub fn get_hostname(&self, flags: Option<u32>) -> Option<String> {
+    let hostname_result = self.domain.get_hostname(flags.unwrap_or(0));
     match hostname_result {
       Ok(hostname) => Some(hostname),
       Err(_) => None,
@@ -494,7 +926,7 @@ impl Machine {
   ///
   /// # Arguments
   ///
-  /// * `flags` - The flags to use for the lookup. Use VirDomainXMLFlags enum.
+  /// * `flags` - The flags to use for the lookup. Use VirDomainXMLFlags enum. Defaults to 0.
   ///
   /// # Returns
   ///
@@ -521,13 +953,51 @@ impl Machine {
   /// getDomainXml();
   /// ```
   #[napi]
-  pub fn get_xml_desc(&self, flags: u32) -> Option<String> {
-    match self.domain.get_xml_desc(flags) {
+  pub fn get_xml_desc(&self, flags: Option<u32>) -> Option<String> {
+    match self.domain.get_xml_desc(flags.unwrap_or(0)) {
       Ok(xml) => Some(xml),
       Err(_) => None,
     }
   }
 
+  /// Get both the live and the persistent XML of the domain.
+  ///
+  /// Together with `diffDomainXml` this tells which configuration changes
+  /// only take effect after the next boot.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `DomainDefinitions` - `live` is `null` when the domain is not running.
+  /// * `null` - If there is an error fetching either definition.
+  ///
+  /// # Example (in JavaScript)
+  ///
+  /// ```javascript
+  /// const { Connection, Machine, diffDomainXml } = require('your-node-package');
+  ///
+  /// async function pendingChanges() {
+  ///   const conn = await Connection.open('qemu:///system');
+  ///   const machine = await Machine.lookupByName(conn, 'your-domain-name');
+  ///   const { live, config } = machine.getDefinitions();
+  ///   if (live !== null) {
+  ///     console.log(diffDomainXml(live, config));
+  ///   }
+  /// }
+  ///
+  /// pendingChanges();
+  /// ```
+  #[napi]
+  pub fn get_definitions(&self) -> Option<DomainDefinitions> {
+    let live = match self.domain.is_active() {
+      Ok(true) => Some(self.domain.get_xml_desc(0).ok()?),
+      Ok(false) => None,
+      Err(_) => return None,
+    };
+    let config = self.domain.get_xml_desc(virt::sys::VIR_DOMAIN_XML_INACTIVE).ok()?;
+    Some(DomainDefinitions { live, config })
+  }
+
   /// Create/power-on the domain.
   ///
   /// # Returns
@@ -562,6 +1032,61 @@ impl Machine {
     }
   }
 
+  /// Create/power-on the domain on a worker thread.
+  ///
+  /// Same as `create`, but the Node.js event loop keeps running while
+  /// libvirt boots the domain. See `tasks.rs` for the threading model.
+  ///
+  /// # Example (in JavaScript)
+  ///
+  /// ```javascript
+  /// const { Connection, Machine } = require('your-node-package');
+  ///
+  /// async function startDomain() {
+  ///   const conn = Connection.open('qemu:///system');
+  ///   const machine = Machine.lookupByName(conn, 'your-domain-name');
+  ///   if ((await machine.createAsync()) === null) {
+  ///     console.error('Failed to start domain');
+  ///   }
+  /// }
+  ///
+  /// startDomain();
+  /// ```
+  #[napi(ts_return_type = "Promise<number | null>")]
+  pub fn create_async(&self) -> AsyncTask<crate::tasks::CreateTask> {
+    AsyncTask::new(crate::tasks::CreateTask::new(self.domain.clone()))
+  }
+
+  /// Create/power-on the domain, throwing the libvirt error on failure.
+  ///
+  /// Same as `create`, but instead of returning `null` it throws an `Error`
+  /// whose `code`, `domain` and `level` properties tell why the call failed.
+  ///
+  /// # Example (in JavaScript)
+  ///
+  /// ```javascript
+  /// const { Connection, Machine, ErrorNumber } = require('your-node-package');
+  ///
+  /// const conn = Connection.open('qemu:///system');
+  /// const machine = Machine.lookupByName(conn, 'your-domain-name');
+  /// try {
+  ///   machine.createChecked();
+  /// } catch (err) {
+  ///   if (err.code === ErrorNumber.OperationInvalid) {
+  ///     console.log('domain is already running');
+  ///   } else {
+  ///     throw err;
+  ///   }
+  /// }
+  /// ```
+  #[napi]
+  pub fn create_checked(&self, env: napi::Env) -> Result<u32> {
+    self
+      .domain
+      .create()
+      .map_err(|err| crate::error::throw_libvirt_error(&env, err))
+  }
+
   /// Create/power-on the domain with flags.
   ///
   /// # Arguments
@@ -725,6 +1250,22 @@ impl Machine {
     }
   }
 
+  /// Define a domain from an XML description, throwing the libvirt error on failure.
+  ///
+  /// Same as `defineXml`, but a bad XML, a name clash or a missing
+  /// permission is thrown as an `Error` carrying the libvirt `code`,
+  /// `domain` and `level` instead of returning `null`.
+  #[napi]
+  pub fn define_xml_checked(env: napi::Env, conn: &Connection, xml: String) -> Result<Machine> {
+    match Domain::define_xml(conn.get_connection(), &xml) {
+      Ok(domain) => Ok(Machine {
+        domain,
+        con: conn.clone(),
+      }),
+      Err(err) => Err(crate::error::throw_libvirt_error(&env, err)),
+    }
+  }
+
   /// Define a domain from an XML description with flags.
   ///
   /// # Arguments
@@ -793,6 +1334,19 @@ impl Machine {
     }
   }
 
+  /// Destroy/power-off the domain, throwing the libvirt error on failure.
+  ///
+  /// Same as `destroy`, but failures are thrown as an `Error` carrying the
+  /// libvirt `code`, `domain` and `level` instead of returning `null`.
+  #[napi]
+  pub fn destroy_checked(&self, env: napi::Env) -> Result<()> {
+    self
+      .domain
+      .destroy()
+      .map(|_| ())
+      .map_err(|err| crate::error::throw_libvirt_error(&env, err))
+  }
+
   /// Reset the domain.
   ///
   /// # Returns
@@ -983,6 +1537,56 @@ impl Machine {
     }
   }
 
+  /// Block until the domain reaches `target_state` or `timeout_ms` elapses.
+  ///
+  /// The state is polled with `getState` every `poll_interval_ms`.
+  ///
+  /// # Arguments
+  ///
+  /// * `target_state` - The state to wait for. Use VirDomainState enum.
+  /// * `timeout_ms` - How long to wait before giving up, in milliseconds.
+  /// * `poll_interval_ms` - Delay between two polls, in milliseconds.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `true` - If the domain reached `target_state`.
+  /// * `false` - If the timeout elapsed first.
+  /// * `null` - If the state could not be read, e.g. the domain went away.
+  ///
+  /// # Example (in JavaScript)
+  ///
+  /// ```javascript
+  /// const { Connection, Machine, VirDomainState } = require('your-node-package');
+  ///
+  /// async function pauseAndWait() {
+  ///   const conn = Connection.open('qemu:///system');
+  ///   const machine = Machine.lookupByName(conn, 'your-domain-name');
+  ///   machine.suspend();
+  ///   if (!machine.waitForState(VirDomainState.VirDomainPaused, 5000, 100)) {
+  ///     console.error('domain did not pause in time');
+  ///   }
+  /// }
+  ///
+  /// pauseAndWait();
+  /// ```
+  #[napi]
+  pub fn wait_for_state(&self, target_state: u32, timeout_ms: u32, poll_interval_ms: u32) -> Option<bool> {
+    let deadline = Instant::now() + Duration::from_millis(timeout_ms as u64);
+    let interval = Duration::from_millis(poll_interval_ms.max(1) as u64);
+    loop {
+      let (state, _reason) = self.domain.get_state().ok()?;
+      if state == target_state {
+        return Some(true);
+      }
+      let now = Instant::now();
+      if now >= deadline {
+        return Some(false);
+      }
+      thread::sleep(interval.min(deadline - now));
+    }
+  }
+
   #[napi]
   pub fn is_active(&self) -> Option<bool> {
     match self.domain.is_active() {
@@ -1044,15 +1648,12 @@ impl Machine {
   }
 
   #[napi]
-  pub fn set_max_memory(&self, memory: BigInt) -> Option<bool> {
-    let (_signed, memory_u64, lossless) = memory.get_u64();
-    if !lossless {
-      return None;
-    }
+  pub fn set_max_memory(&self, memory: BigInt) -> Result<Option<bool>> {
+    let memory_u64 = bigint_to_u64(&memory)?;
     let result = self.domain.set_max_memory(memory_u64);
     match result {
-      Ok(result) => Some(result),
-      Err(_) => None,
+      Ok(result) => Ok(Some(result)),
+      Err(_) => Ok(None),
     }
   }
 
@@ -1065,15 +1666,12 @@ impl Machine {
   }
 
   #[napi]
-  pub fn set_memory(&self, memory: BigInt) -> Option<bool> {
-    let (_signed, memory_u64, lossless) = memory.get_u64();
-    if !lossless {
-      return None;
-    }
+  pub fn set_memory(&self, memory: BigInt) -> Result<Option<bool>> {
+    let memory_u64 = bigint_to_u64(&memory)?;
     let result = self.domain.set_memory(memory_u64);
     match result {
-      Ok(result) => Some(result),
-      Err(_) => None,
+      Ok(result) => Ok(Some(result)),
+      Err(_) => Ok(None),
     }
   }
 
@@ -1082,15 +1680,12 @@ impl Machine {
   ///
   /// * `flags` - The flags to use for the memory modification. Use VirDomainMemoryModFlags enum
   #[napi]
-  pub fn set_memory_flags(&self, memory: BigInt, flags: u32) -> Option<bool> {
-    let (_signed, memory_u64, lossless) = memory.get_u64();
-    if !lossless {
-      return None;
-    }
+  pub fn set_memory_flags(&self, memory: BigInt, flags: u32) -> Result<Option<bool>> {
+    let memory_u64 = bigint_to_u64(&memory)?;
     let result = self.domain.set_memory_flags(memory_u64, flags as u32);
     match result {
-      Ok(result) => Some(result),
-      Err(_) => None,
+      Ok(result) => Ok(Some(result)),
+      Err(_) => Ok(None),
     }
   }
 
@@ -1114,6 +1709,21 @@ impl Machine {
     }
   }
 
+  /// Get the maximum number of vCPUs in the persistent definition of the domain,
+  /// i.e. the upper bound for hot-plugging vCPUs with `setVcpusFlags`.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `number` - The configured maximum vCPU count.
+  /// * `null` - If the domain is transient or there is an error.
+  #[napi]
+  pub fn get_max_vcpus_config(&self) -> Option<u32> {
+    self.get_vcpus_flags(Some(
+      virt::sys::VIR_DOMAIN_VCPU_MAXIMUM | virt::sys::VIR_DOMAIN_AFFECT_CONFIG,
+    ))
+  }
+
   #[napi]
   pub fn set_vcpus_flags(&self, vcpus: u32, flags: u32) -> Option<bool> {
     match self.domain.set_vcpus_flags(vcpus, flags) {
@@ -1130,6 +1740,7 @@ impl Machine {
     }
   }
 
+  /// Restore a domain saved with `save`. See VirDomainSaveRestoreFlags.
   #[napi]
   pub fn domain_restore_flags(conn: &Connection, path: String, flags: u32) -> Option<u32> {
     match  Domain::domain_restore_flags(conn.get_connection(), &path, None, flags) {
@@ -1138,24 +1749,131 @@ impl Machine {
     }
   }
 
+  /// Restore a domain saved with `save`, replacing its definition.
+  ///
+  /// # Arguments
+  ///
+  /// * `conn` - The connection to restore the domain on.
+  /// * `path` - The saved image.
+  /// * `dxml` - The domain XML to use instead of the one stored in the image.
+  ///   Only host specific parts, such as disk paths, may differ.
+  /// * `flags` - See VirDomainSaveRestoreFlags.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `0` - If the domain was restored.
+  /// * `null` - If there is an error.
+  #[napi]
+  pub fn domain_restore_with_dxml(conn: &Connection, path: String, dxml: String, flags: u32) -> Option<u32> {
+    match Domain::domain_restore_flags(conn.get_connection(), &path, Some(&dxml), flags) {
+      Ok(_) => Some(0),
+      Err(_) => None,
+    }
+  }
+
   #[napi]
-  pub fn get_vcpus_flags(&self, flags: u32) -> Option<u32> {
-    match self.domain.get_vcpus_flags(flags) {
+  pub fn get_vcpus_flags(&self, flags: Option<u32>) -> Option<u32> {
+    match self.domain.get_vcpus_flags(flags.unwrap_or(0)) {
       Ok(vcpus) => Some(vcpus),
       Err(_) => None,
     }
   }
 
+  /// Get the CPU time used by the domain, in total or per host CPU.
+  ///
+  /// # Arguments
+  ///
+  /// * `total` - Whether to return a single aggregate entry instead of one
+  ///   entry per host CPU.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `CpuStat[]` - One entry, or one entry per host CPU the domain has run on.
+  /// * `null` - If there is an error while retrieving the statistics.
+  ///
+  /// # Example (in JavaScript)
+  ///
+  /// ```javascript
+  /// for (const stat of machine.getCpuStats(false) ?? []) {
+  ///   console.log(`cpu ${stat.cpu}: ${stat.vcpuTime} ns`);
+  /// }
+  /// ```
   #[napi]
-  pub fn migrate_set_max_speed(&self, bandwidth: BigInt, flags: u32) -> Option<u32> {
-    let (_signed, bandwidth_u64, lossless) = bandwidth.get_u64();
-    if !lossless {
+  pub fn get_cpu_stats(&self, total: bool) -> Option<Vec<CpuStat>> {
+    let dom = self.domain.as_ptr();
+    // libvirt first reports how many parameters (and host CPUs) to allocate for.
+    let (start_cpu, ncpus) = if total {
+      (-1, 1)
+    } else {
+      let ncpus = unsafe { virt::sys::virDomainGetCPUStats(dom, std::ptr::null_mut(), 0, 0, 0, 0) };
+      if ncpus < 0 {
+        return None;
+      }
+      if ncpus == 0 {
+        return Some(Vec::new());
+      }
+      (0, ncpus as u32)
+    };
+    let nparams = unsafe {
+      virt::sys::virDomainGetCPUStats(dom, std::ptr::null_mut(), 0, start_cpu, 1, 0)
+    };
+    if nparams < 0 {
       return None;
     }
+
+    let nparams = nparams as u32;
+    // The remote driver rejects requests for more than 128 CPUs or 2048
+    // parameters at once, so larger hosts are read in chunks like virsh does.
+    let chunk_size = (2048 / nparams.max(1)).clamp(1, 128);
+
+    let mut stats = Vec::new();
+    let mut chunk_start = start_cpu;
+    let mut remaining = ncpus;
+    while remaining > 0 {
+      let chunk_cpus = remaining.min(chunk_size);
+      let mut params: Vec<virt::sys::virTypedParameter> = (0..nparams * chunk_cpus)
+        .map(|_| unsafe { std::mem::zeroed() })
+        .collect();
+      let filled = unsafe {
+        virt::sys::virDomainGetCPUStats(dom, params.as_mut_ptr(), nparams, chunk_start, chunk_cpus, 0)
+      };
+      if filled < 0 {
+        return None;
+      }
+
+      for (offset, chunk) in params.chunks_mut(nparams.max(1) as usize).enumerate() {
+        let values = unsafe { crate::typed_params::from_raw(chunk.as_mut_ptr(), filled.min(nparams as i32)) };
+        let field = |name: &str| crate::typed_params::find(&values, name).and_then(|value| value.as_u64());
+        // Host CPUs the domain never ran on come back without any parameter.
+        let cpu_time = match field("cpu_time") {
+          Some(cpu_time) => cpu_time,
+          None => continue,
+        };
+        stats.push(CpuStat {
+          cpu: if total { None } else { Some(chunk_start as u32 + offset as u32) },
+          cpu_time: cpu_time.into(),
+          user_time: field("user_time").map(BigInt::from),
+          system_time: field("system_time").map(BigInt::from),
+          vcpu_time: field("vcpu_time").map(BigInt::from),
+        });
+      }
+      unsafe { virt::sys::virTypedParamsClear(params.as_mut_ptr(), params.len() as i32) };
+
+      chunk_start += chunk_cpus as i32;
+      remaining -= chunk_cpus;
+    }
+    Some(stats)
+  }
+
+  #[napi]
+  pub fn migrate_set_max_speed(&self, bandwidth: BigInt, flags: u32) -> Result<Option<u32>> {
+    let bandwidth_u64 = bigint_to_u64(&bandwidth)?;
     let result = self.domain.migrate_set_max_speed(bandwidth_u64, flags);
     match result {
-      Ok(result) => Some(result),
-      Err(_) => None,
+      Ok(result) => Ok(Some(result)),
+      Err(_) => Ok(None),
     }
   }
 
@@ -1168,15 +1886,12 @@ impl Machine {
   }
 
   #[napi]
-  pub fn migrate_set_compression_cache(&self, size: BigInt, flags: u32) -> Option<u32> {
-    let (_signed, size_u64, lossless) = size.get_u64();
-    if !lossless {
-      return None;
-    }
+  pub fn migrate_set_compression_cache(&self, size: BigInt, flags: u32) -> Result<Option<u32>> {
+    let size_u64 = bigint_to_u64(&size)?;
     let result = self.domain.migrate_set_compression_cache(size_u64, flags);
     match result {
-      Ok(result) => Some(result),
-      Err(_) => None,
+      Ok(result) => Ok(Some(result)),
+      Err(_) => Ok(None),
     }
   }
 
@@ -1189,29 +1904,216 @@ impl Machine {
   }
 
   #[napi]
-  pub fn migrate_set_max_downtime(&self, downtime: BigInt, flags: u32) -> Option<u32> {
-    let (_signed, downtime_u64, lossless) = downtime.get_u64();
-    if !lossless {
-      return None;
-    }
+  pub fn migrate_set_max_downtime(&self, downtime: BigInt, flags: u32) -> Result<Option<u32>> {
+    let downtime_u64 = bigint_to_u64(&downtime)?;
     let result = self.domain.migrate_set_max_downtime(downtime_u64, flags);
     match result {
-      Ok(result) => Some(result),
-      Err(_) => None,
+      Ok(result) => Ok(Some(result)),
+      Err(_) => Ok(None),
     }
   }
 
-  #[napi]
-  pub fn set_time(&self, seconds: i64, nseconds: i32, flags: u32) -> Option<u32> {
-    match self.domain.set_time(seconds, nseconds, flags) {
-      Ok(result) => Some(result),
-      Err(_) => None,
-    }
+  /// Configure the migration convergence knobs in a single call.
+  ///
+  /// Only the provided values are applied; `null`/`undefined` values are left
+  /// untouched. The values are applied in order: max speed, max downtime and
+  /// compression cache, stopping at the first failure.
+  ///
+  /// # Arguments
+  ///
+  /// * `max_speed` - Maximum migration bandwidth in MiB/s.
+  /// * `max_downtime` - Maximum tolerable downtime in milliseconds.
+  /// * `compression_cache` - Size of the compression cache in bytes.
+  /// * `flags` - Flags passed to the max speed update (e.g. post-copy bandwidth).
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `0` - If every provided value was applied.
+  /// * `null` - If libvirt rejected one of the updates.
+  ///
+  /// Throws if one of the values does not fit in an unsigned 64-bit integer.
+  ///
+  /// # Example (in JavaScript)
+  ///
+  /// ```javascript
+  /// const { Connection, Machine } = require('your-node-package');
+  ///
+  /// async function tuneMigration() {
+  ///   const conn = Connection.open('qemu:///system');
+  ///   const machine = Machine.lookupByName(conn, 'your-domain-name');
+  ///   machine.configureMigration(BigInt(1000), BigInt(300), null, 0);
+  /// }
+  ///
+  /// tuneMigration();
+  /// ```
+  #[napi]
+  pub fn configure_migration(
+    &self,
+    max_speed: Option<BigInt>,
+    max_downtime: Option<BigInt>,
+    compression_cache: Option<BigInt>,
+    flags: u32,
+  ) -> Result<Option<u32>> {
+    if let Some(speed) = max_speed {
+      if self.migrate_set_max_speed(speed, flags)?.is_none() {
+        return Ok(None);
+      }
+    }
+    if let Some(downtime) = max_downtime {
+      if self.migrate_set_max_downtime(downtime, 0)?.is_none() {
+        return Ok(None);
+      }
+    }
+    if let Some(size) = compression_cache {
+      if self.migrate_set_compression_cache(size, 0)?.is_none() {
+        return Ok(None);
+      }
+    }
+    Ok(Some(0))
+  }
+
+  /// Switch an ongoing pre-copy migration to post-copy mode.
+  ///
+  /// The migration must have been started with the post-copy migrate flag.
+  /// Once switched, the domain runs on the destination and the remaining
+  /// memory is pulled on demand, which guarantees the migration converges.
+  ///
+  /// # Arguments
+  ///
+  /// * `flags` - Extra flags, currently unused by libvirt. Pass 0.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `0` - If the switch to post-copy was requested.
+  /// * `null` - If there is an error, e.g. no post-copy capable migration is running.
+  ///
+  /// # Example (in JavaScript)
+  ///
+  /// ```javascript
+  /// const { Connection, Machine } = require('your-node-package');
+  ///
+  /// async function forceConvergence() {
+  ///   const conn = Connection.open('qemu:///system');
+  ///   const machine = Machine.lookupByName(conn, 'your-domain-name');
+  ///   machine.migrateStartPostCopy(0);
+  /// }
+  ///
+  /// forceConvergence();
+  /// ```
+  #[napi]
+  pub fn migrate_start_post_copy(&self, flags: u32) -> Option<u32> {
+    let ret = unsafe { virt::sys::virDomainMigrateStartPostCopy(self.domain.as_ptr(), flags) };
+    if ret == -1 {
+      return None;
+    }
+    Some(0)
+  }
+
+  /// Start calculating the memory dirty rate of the domain.
+  ///
+  /// The calculation runs in the background for `seconds` seconds. Once it
+  /// completes, the result is reported through the `dirtyrate.*` fields of the
+  /// DIRTYRATE domain stats group (`Connection.getAllDomainStats`) and by
+  /// `getDirtyRate`.
+  ///
+  /// # Arguments
+  ///
+  /// * `seconds` - The duration of the measurement, in seconds.
+  /// * `flags` - The calculation mode, see VirDomainDirtyRateCalcFlags.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `0` - If the calculation was started.
+  /// * `null` - If there is an error starting the calculation.
+  ///
+  /// # Example (in JavaScript)
+  ///
+  /// ```javascript
+  /// const { Connection, Machine } = require('your-node-package');
+  ///
+  /// async function measureDirtyRate() {
+  ///   const conn = Connection.open('qemu:///system');
+  ///   const machine = Machine.lookupByName(conn, 'your-domain-name');
+  ///   machine.startDirtyRateCalc(1, 0);
+  /// }
+  ///
+  /// measureDirtyRate();
+  /// ```
+  #[napi]
+  pub fn start_dirty_rate_calc(&self, seconds: i32, flags: u32) -> Option<u32> {
+    let ret = unsafe {
+      virt::sys::virDomainStartDirtyRateCalc(self.domain.as_ptr(), seconds, flags)
+    };
+    if ret == -1 {
+      return None;
+    }
+    Some(0)
+  }
+
+  /// Get the result of the last memory dirty rate calculation of the domain.
+  ///
+  /// Reads the DIRTYRATE stats group of this domain only.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `DirtyRate` - The state of the calculation; `calcStatus` is 2
+  ///   (VirDomainDirtyRateStatus.VirDomainDirtyRateMeasured) once
+  ///   `megabytesPerSecond` is valid.
+  /// * `null` - If there is an error or the hypervisor does not report a dirty rate.
+  ///
+  /// # Example (in JavaScript)
+  ///
+  /// ```javascript
+  /// machine.startDirtyRateCalc(2, 0);
+  /// setTimeout(() => {
+  ///   const rate = machine.getDirtyRate();
+  ///   if (rate?.calcStatus === VirDomainDirtyRateStatus.VirDomainDirtyRateMeasured) {
+  ///     console.log(`guest dirties ${rate.megabytesPerSecond} MiB/s`);
+  ///   }
+  /// }, 2500);
+  /// ```
+  #[napi]
+  pub fn get_dirty_rate(&self) -> Option<crate::domain_stats_record::DirtyRate> {
+    let mut doms = [self.domain.as_ptr(), std::ptr::null_mut()];
+    let mut records: *mut virt::sys::virDomainStatsRecordPtr = std::ptr::null_mut();
+    let count = unsafe {
+      virt::sys::virDomainListGetStats(
+        doms.as_mut_ptr(),
+        virt::sys::VIR_DOMAIN_STATS_DIRTYRATE,
+        &mut records,
+        0,
+      )
+    };
+    if count < 0 {
+      return None;
+    }
+    let params = if count > 0 {
+      unsafe {
+        let record = &**records;
+        crate::typed_params::from_raw(record.params, record.nparams)
+      }
+    } else {
+      Vec::new()
+    };
+    unsafe { virt::sys::virDomainStatsRecordListFree(records) };
+    crate::domain_stats_record::DirtyRate::from_params(&params)
+  }
+
+  #[napi]
+  pub fn set_time(&self, seconds: i64, nseconds: i32, flags: u32) -> Option<u32> {
+    match self.domain.set_time(seconds, nseconds, flags) {
+      Ok(result) => Some(result),
+      Err(_) => None,
+    }
   }
 
   #[napi]
-  pub fn get_time(&self, flags: u32) -> Option<Time> {
-    match self.domain.get_time(flags) {
+  pub fn get_time(&self, flags: Option<u32>) -> Option<Time> {
+    match self.domain.get_time(flags.unwrap_or(0)) {
       Ok(result) => Some(Time {
         seconds: result.0,
         nseconds: result.1,
@@ -1221,8 +2123,8 @@ impl Machine {
   }
 
   #[napi]
-  pub fn get_block_info(&self, disk: String, flags: u32) -> Option<BlockInfo> {
-    match self.domain.get_block_info(&disk, flags) {
+  pub fn get_block_info(&self, disk: String, flags: Option<u32>) -> Option<BlockInfo> {
+    match self.domain.get_block_info(&disk, flags.unwrap_or(0)) {
       Ok(result) => Some(BlockInfo {
         capacity: result.capacity.into(),
         allocation: result.allocation.into(),
@@ -1232,6 +2134,73 @@ impl Machine {
     }
   }
 
+  /// Get the state, current host CPU and affinity of every vCPU of the domain.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `VcpuInfo[]` - One entry per active vCPU.
+  /// * `null` - If the domain is not running or there is an error.
+  ///
+  /// # Example (in JavaScript)
+  ///
+  /// ```javascript
+  /// machine.pinVcpu(0, Buffer.from([0b0010]));
+  /// const [vcpu0] = machine.getVcpus();
+  /// console.log(vcpu0.affinity); // [false, true, false, ...]
+  /// ```
+  #[napi]
+  pub fn get_vcpus(&self) -> Option<Vec<VcpuInfo>> {
+    let dom = self.domain.as_ptr();
+    let maxinfo = self.domain.get_info().ok()?.nr_virt_cpu as i32;
+    let host_cpus = unsafe {
+      virt::sys::virNodeGetCPUMap(
+        virt::sys::virDomainGetConnect(dom),
+        std::ptr::null_mut(),
+        std::ptr::null_mut(),
+        0,
+      )
+    };
+    if maxinfo <= 0 || host_cpus < 0 {
+      return None;
+    }
+    let maplen = (host_cpus as usize + 7) / 8;
+
+    let mut info: Vec<virt::sys::virVcpuInfo> = (0..maxinfo)
+      .map(|_| unsafe { std::mem::zeroed() })
+      .collect();
+    let mut cpumaps = vec![0u8; maxinfo as usize * maplen];
+    let count = unsafe {
+      virt::sys::virDomainGetVcpus(
+        dom,
+        info.as_mut_ptr(),
+        maxinfo,
+        cpumaps.as_mut_ptr(),
+        maplen as i32,
+      )
+    };
+    if count < 0 {
+      return None;
+    }
+
+    Some(
+      info
+        .iter()
+        .zip(cpumaps.chunks(maplen))
+        .take(count as usize)
+        .map(|(vcpu, map)| VcpuInfo {
+          number: vcpu.number,
+          state: vcpu.state,
+          cpu_time: BigInt::from(vcpu.cpuTime),
+          cpu: vcpu.cpu,
+          affinity: (0..host_cpus as usize)
+            .map(|cpu| map[cpu / 8] & (1 << (cpu % 8)) != 0)
+            .collect(),
+        })
+        .collect(),
+    )
+  }
+
   #[napi]
   pub fn pin_vcpu(&self, vcpu: u32, cpumap: &[u8]) -> Option<u32> {
     match self.domain.pin_vcpu(vcpu, cpumap) {
@@ -1256,6 +2225,130 @@ impl Machine {
     }
   }
 
+  /// Get the I/O threads of the domain and their CPU affinity.
+  ///
+  /// # Arguments
+  ///
+  /// * `flags` - See VirDomainModificationImpact.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `IOThreadInfo[]` - One entry per I/O thread, empty if the domain has none.
+  /// * `null` - If there is an error.
+  #[napi]
+  pub fn get_iothread_info(&self, flags: u32) -> Option<Vec<IOThreadInfo>> {
+    let dom = self.domain.as_ptr();
+    let mut info: *mut virt::sys::virDomainIOThreadInfoPtr = std::ptr::null_mut();
+    let count = unsafe { virt::sys::virDomainGetIOThreadInfo(dom, &mut info, flags) };
+    if count < 0 {
+      return None;
+    }
+    let host_cpus = unsafe {
+      virt::sys::virNodeGetCPUMap(
+        virt::sys::virDomainGetConnect(dom),
+        std::ptr::null_mut(),
+        std::ptr::null_mut(),
+        0,
+      )
+    };
+    let threads = (0..count as usize)
+      .map(|i| unsafe {
+        let entry = *info.add(i);
+        let map = std::slice::from_raw_parts((*entry).cpumap, (*entry).cpumaplen as usize);
+        let ncpus = if host_cpus > 0 { host_cpus as usize } else { map.len() * 8 };
+        let thread = IOThreadInfo {
+          iothread_id: (*entry).iothread_id,
+          cpumap: (0..ncpus.min(map.len() * 8))
+            .map(|cpu| map[cpu / 8] & (1 << (cpu % 8)) != 0)
+            .collect(),
+        };
+        virt::sys::virDomainIOThreadInfoFree(entry);
+        thread
+      })
+      .collect();
+    unsafe { free_libvirt_memory(info) };
+    Some(threads)
+  }
+
+  /// Pin an I/O thread of the domain to a set of host CPUs.
+  ///
+  /// # Arguments
+  ///
+  /// * `iothread_id` - ID of the I/O thread.
+  /// * `cpumap` - Bitmap of host CPUs, one bit per CPU with CPU 0 in the lowest
+  ///   bit of the first byte.
+  /// * `flags` - See VirDomainModificationImpact.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `0` - If the thread was pinned.
+  /// * `null` - If there is an error.
+  ///
+  /// # Example (in JavaScript)
+  ///
+  /// ```javascript
+  /// // Keep disk I/O of thread 1 on host CPUs 2 and 3.
+  /// machine.pinIothread(1, Buffer.from([0b00001100]), VirDomainModificationImpact.VirDomainAffectLive);
+  /// ```
+  #[napi]
+  pub fn pin_iothread(&self, iothread_id: u32, cpumap: &[u8], flags: u32) -> Option<u32> {
+    let ret = unsafe {
+      virt::sys::virDomainPinIOThread(
+        self.domain.as_ptr(),
+        iothread_id,
+        cpumap.as_ptr() as *mut u8,
+        cpumap.len() as i32,
+        flags,
+      )
+    };
+    if ret < 0 {
+      return None;
+    }
+    Some(0)
+  }
+
+  /// Add an I/O thread to the domain.
+  ///
+  /// # Arguments
+  ///
+  /// * `iothread_id` - ID of the new I/O thread; must not be in use.
+  /// * `flags` - See VirDomainModificationImpact.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `0` - If the thread was added.
+  /// * `null` - If there is an error.
+  #[napi]
+  pub fn add_iothread(&self, iothread_id: u32, flags: u32) -> Option<u32> {
+    if unsafe { virt::sys::virDomainAddIOThread(self.domain.as_ptr(), iothread_id, flags) } < 0 {
+      return None;
+    }
+    Some(0)
+  }
+
+  /// Remove an I/O thread from the domain. Fails if a disk still uses it.
+  ///
+  /// # Arguments
+  ///
+  /// * `iothread_id` - ID of the I/O thread.
+  /// * `flags` - See VirDomainModificationImpact.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `0` - If the thread was removed.
+  /// * `null` - If there is an error.
+  #[napi]
+  pub fn del_iothread(&self, iothread_id: u32, flags: u32) -> Option<u32> {
+    if unsafe { virt::sys::virDomainDelIOThread(self.domain.as_ptr(), iothread_id, flags) } < 0 {
+      return None;
+    }
+    Some(0)
+  }
+
   #[napi]
   pub fn rename(&self, new_name: String, flags: u32) -> Option<u32> {
     match self.domain.rename(&new_name, flags) {
@@ -1273,15 +2366,12 @@ impl Machine {
   }
 
   #[napi]
-  pub fn set_block_threshold(&self, dev: String, threshold: BigInt, flags: u32) -> Option<u32> {
-    let (_signed, threshold_u64, lossless) = threshold.get_u64();
-    if !lossless {
-      return None;
-    }
+  pub fn set_block_threshold(&self, dev: String, threshold: BigInt, flags: u32) -> Result<Option<u32>> {
+    let threshold_u64 = bigint_to_u64(&threshold)?;
     let result = self.domain.set_block_threshold(&dev, threshold_u64, flags);
     match result {
-      Ok(result) => Some(result),
-      Err(_) => None,
+      Ok(result) => Ok(Some(result)),
+      Err(_) => Ok(None),
     }
   }
 
@@ -1301,11 +2391,54 @@ impl Machine {
     }
   }
 
-  // TODO: implement open_channel, we need to check Stream struct and implement tons of things
-  // before being able to implement this method
+  /// Open the text console of the domain.
+  ///
+  /// # Arguments
+  ///
+  /// * `dev_name` - The console, serial or parallel port device alias, or `null`
+  ///   for the first console.
+  /// * `flags` - See VirDomainConsoleFlags.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `Stream` - A blocking stream connected to the console.
+  /// * `null` - If the console could not be opened.
+  ///
+  /// # Example (in JavaScript)
+  ///
+  /// ```javascript
+  /// const console = machine.openConsole(null, 0);
+  /// let chunk;
+  /// while ((chunk = console.recv(4096)) && chunk.length > 0) {
+  ///   process.stdout.write(chunk);
+  /// }
+  /// console.finish();
+  /// ```
+  #[napi]
+  pub fn open_console(&self, dev_name: Option<String>, flags: u32) -> Option<crate::stream::Stream> {
+    let stream = crate::stream::Stream::create(&self.con, None)?;
+    let dev_name = match dev_name {
+      Some(name) => Some(std::ffi::CString::new(name).ok()?),
+      None => None,
+    };
+    let ret = unsafe {
+      virt::sys::virDomainOpenConsole(
+        self.domain.as_ptr(),
+        dev_name.as_ref().map_or(std::ptr::null(), |name| name.as_ptr()),
+        stream.get().as_ptr(),
+        flags,
+      )
+    };
+    if ret < 0 {
+      return None;
+    }
+    Some(stream)
+  }
+
+  // TODO: implement open_channel on top of crate::stream::Stream like open_console
 //   #[napi]
 //   pub fn open_channel(&self, name: &str, stream: &Stream, flags: u32) -> Result<u32, Error> {
-    // pub fn open_console(&self, name: &str, stream: &Stream, flags: u32) -> Result<u32, Error> {
 
 //   #[napi]
 //   pub fn interface_addresses(
@@ -1317,23 +2450,65 @@ impl Machine {
   #[napi]
   pub fn interface_stats(&self, path: String) -> Option<InterfaceStats> {
     match self.domain.interface_stats(&path) {
-      Ok(stats) => Some(InterfaceStats {
-        rx_bytes: stats.rx_bytes,
-        rx_packets: stats.rx_packets,
-        rx_errs: stats.rx_errs,
-        rx_drop: stats.rx_drop,
-        tx_bytes: stats.tx_bytes,
-        tx_packets: stats.tx_packets,
-        tx_errs: stats.tx_errs,
-        tx_drop: stats.tx_drop,
-      }),
+      Ok(stats) => Some(InterfaceStats::from(stats)),
       Err(_) => None,
     }
   }
 
+  /// Get the I/O counters of a block device of the domain.
+  ///
+  /// # Arguments
+  ///
+  /// * `path` - The target device name, e.g. `vda`, or the path of its source.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `BlockStats` - The counters, cumulative since the domain started. They may
+  ///   reset when the domain is migrated.
+  /// * `null` - If the domain is not running or the device does not exist.
+  ///
+  /// # Example (in JavaScript)
+  ///
+  /// ```javascript
+  /// const stats = machine.blockStats('vda');
+  /// if (stats) {
+  ///   console.log(`read ${stats.rdBytes} bytes, wrote ${stats.wrBytes} bytes`);
+  /// }
+  /// ```
+  #[napi]
+  pub fn block_stats(&self, path: String) -> Option<BlockStats> {
+    let path = std::ffi::CString::new(path).ok()?;
+    let mut stats = virt::sys::virDomainBlockStatsStruct {
+      rd_req: 0,
+      rd_bytes: 0,
+      wr_req: 0,
+      wr_bytes: 0,
+      errs: 0,
+    };
+    let ret = unsafe {
+      virt::sys::virDomainBlockStats(
+        self.domain.as_ptr(),
+        path.as_ptr(),
+        &mut stats,
+        std::mem::size_of::<virt::sys::virDomainBlockStatsStruct>(),
+      )
+    };
+    if ret < 0 {
+      return None;
+    }
+    Some(BlockStats {
+      rd_req: stats.rd_req,
+      rd_bytes: stats.rd_bytes,
+      wr_req: stats.wr_req,
+      wr_bytes: stats.wr_bytes,
+      errs: stats.errs,
+    })
+  }
+
   #[napi]
-  pub fn memory_stats(&self, flags: u32) -> Option<Vec<MemoryStat>> {
-    match self.domain.memory_stats(flags) {
+  pub fn memory_stats(&self, flags: Option<u32>) -> Option<Vec<MemoryStat>> {
+    match self.domain.memory_stats(flags.unwrap_or(0)) {
       Ok(stats) => {
         let mut memory_stats = Vec::new();
         for stat in stats {
@@ -1348,6 +2523,44 @@ impl Machine {
     }
   }
 
+  /// Get the balloon size and memory usage of the domain as named fields.
+  ///
+  /// Guest side values (`available`, `usable`, `unused`) require a balloon
+  /// driver in the guest and are refreshed every `setMemoryStatsPeriod` seconds.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `BalloonInfo` - The memory statistics in KiB.
+  /// * `null` - If there is an error while retrieving the statistics.
+  ///
+  /// # Example (in JavaScript)
+  ///
+  /// ```javascript
+  /// machine.setMemoryStatsPeriod(5, 0);
+  /// const { actual, usable } = machine.getBalloonInfo();
+  /// if (usable !== null && usable * 10n < actual) {
+  ///   console.log('guest is under memory pressure');
+  /// }
+  /// ```
+  #[napi]
+  pub fn get_balloon_info(&self) -> Option<BalloonInfo> {
+    let stats = self.domain.memory_stats(0).ok()?;
+    let stat = |tag: u32| {
+      stats
+        .iter()
+        .find(|stat| stat.tag == tag)
+        .map(|stat| BigInt::from(stat.val))
+    };
+    Some(BalloonInfo {
+      actual: stat(virt::sys::VIR_DOMAIN_MEMORY_STAT_ACTUAL_BALLOON),
+      rss: stat(virt::sys::VIR_DOMAIN_MEMORY_STAT_RSS),
+      available: stat(virt::sys::VIR_DOMAIN_MEMORY_STAT_AVAILABLE),
+      usable: stat(virt::sys::VIR_DOMAIN_MEMORY_STAT_USABLE),
+      unused: stat(virt::sys::VIR_DOMAIN_MEMORY_STAT_UNUSED),
+    })
+  }
+
   #[napi]
   pub fn save_image_get_xml_desc(
     conn: &Connection,
@@ -1381,6 +2594,7 @@ impl Machine {
     }
   }
 
+  /// `flags` is a combination of VirDomainDeviceModifyFlags.
   #[napi]
   pub fn attach_device_flags(&self, xml: String, flags: u32) -> Option<u32> {
     match self.domain.attach_device_flags(&xml, flags) {
@@ -1397,6 +2611,7 @@ impl Machine {
     }
   }
 
+  /// `flags` is a combination of VirDomainDeviceModifyFlags.
   #[napi]
   pub fn detach_device_flags(&self, xml: String, flags: u32) -> Option<u32> {
     match self.domain.detach_device_flags(&xml, flags) {
@@ -1405,119 +2620,1342 @@ impl Machine {
     }
   }
 
+  /// `flags` is a combination of VirDomainDeviceModifyFlags.
+  #[napi]
+  pub fn update_device_flags(&self, xml: String, flags: u32) -> Option<u32> {
+    match self.domain.update_device_flags(&xml, flags) {
+      Ok(result) => Some(result),
+      Err(_) => None,
+    }
+  }
+
+  /// Attach a disk image or block device to the domain.
+  ///
+  /// The bus is chosen from the target name: `vd*` is virtio, `sd*` SCSI,
+  /// `hd*` IDE and `xvd*` Xen. Paths under `/dev/` are attached as block
+  /// devices, anything else as a file.
+  ///
+  /// # Arguments
+  ///
+  /// * `source_path` - Path of the image or block device on the host.
+  /// * `target_dev` - Device name in the guest, e.g. `vdb`.
+  /// * `format` - Image format, e.g. `qcow2` or `raw`.
+  /// * `flags` - See VirDomainDeviceModifyFlags.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `0` - If the disk was attached.
+  /// * `null` - If there is an error.
+  ///
+  /// # Example (in JavaScript)
+  ///
+  /// ```javascript
+  /// machine.attachDisk(
+  ///   '/var/lib/libvirt/images/data.qcow2',
+  ///   'vdb',
+  ///   'qcow2',
+  ///   VirDomainDeviceModifyFlags.VirDomainDeviceModifyLive | VirDomainDeviceModifyFlags.VirDomainDeviceModifyConfig,
+  /// );
+  /// ```
+  #[napi]
+  pub fn attach_disk(&self, source_path: String, target_dev: String, format: String, flags: u32) -> Option<u32> {
+    let (kind, source_attr) = if source_path.starts_with("/dev/") {
+      ("block", "dev")
+    } else {
+      ("file", "file")
+    };
+    let bus = match disk_bus_for_target(&target_dev) {
+      Some(bus) => format!(" bus='{}'", bus),
+      None => String::new(),
+    };
+    let xml = format!(
+      "<disk type='{}' device='disk'><driver name='qemu' type='{}'/><source {}='{}'/><target dev='{}'{}/></disk>",
+      kind,
+      crate::xml::escape(&format),
+      source_attr,
+      crate::xml::escape(&source_path),
+      crate::xml::escape(&target_dev),
+      bus
+    );
+    self.attach_device_flags(xml, flags)
+  }
+
+  /// Detach the disk with the given target name from the domain.
+  ///
+  /// # Arguments
+  ///
+  /// * `target_dev` - Device name in the guest, e.g. `vdb`.
+  /// * `flags` - See VirDomainDeviceModifyFlags.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `0` - If the disk was detached, or the guest was asked to release it.
+  /// * `null` - If there is an error, e.g. no disk uses `target_dev`.
+  #[napi]
+  pub fn detach_disk(&self, target_dev: String, flags: u32) -> Option<u32> {
+    let xml = format!(
+      "<disk type='file' device='disk'><target dev='{}'/></disk>",
+      crate::xml::escape(&target_dev)
+    );
+    self.detach_device_flags(xml, flags)
+  }
+
+  /// Attach a network interface connected to a libvirt network.
+  ///
+  /// # Arguments
+  ///
+  /// * `network_name` - Name of the libvirt network, e.g. `default`.
+  /// * `mac` - MAC address of the interface, or `null` to let libvirt generate one.
+  /// * `model` - NIC model, e.g. `virtio` or `e1000`, or `null` for the hypervisor default.
+  /// * `flags` - See VirDomainDeviceModifyFlags.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `0` - If the interface was attached.
+  /// * `null` - If there is an error.
+  ///
+  /// # Example (in JavaScript)
+  ///
+  /// ```javascript
+  /// machine.attachInterface('default', null, 'virtio', VirDomainDeviceModifyFlags.VirDomainDeviceModifyLive);
+  /// ```
+  #[napi]
+  pub fn attach_interface(
+    &self,
+    network_name: String,
+    mac: Option<String>,
+    model: Option<String>,
+    flags: u32,
+  ) -> Option<u32> {
+    let mac = mac
+      .map(|mac| format!("<mac address='{}'/>", crate::xml::escape(&mac)))
+      .unwrap_or_default();
+    let model = model
+      .map(|model| format!("<model type='{}'/>", crate::xml::escape(&model)))
+      .unwrap_or_default();
+    let xml = format!(
+      "<interface type='network'><source network='{}'/>{}{}</interface>",
+      crate::xml::escape(&network_name),
+      mac,
+      model
+    );
+    self.attach_device_flags(xml, flags)
+  }
+
+  /// Save the memory of the running domain to a file and stop it. Use
+  /// `domainRestore` to start it again from that file.
+  ///
+  /// # Arguments
+  ///
+  /// * `to` - Path of the file to write.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `0` - If the domain was saved.
+  /// * `null` - If there is an error.
+  #[napi]
+  pub fn save(&self, to: String) -> Option<u32> {
+    let to = std::ffi::CString::new(to).ok()?;
+    match unsafe { virt::sys::virDomainSave(self.domain.as_ptr(), to.as_ptr()) } {
+      -1 => None,
+      _ => Some(0),
+    }
+  }
+
+  /// Save the memory of the running domain to a file and stop it.
+  ///
+  /// # Arguments
+  ///
+  /// * `to` - Path of the file to write.
+  /// * `dxml` - Domain XML to store in the image instead of the current one, or `null`.
+  ///   Only host specific parts, such as disk paths, may differ.
+  /// * `flags` - See VirDomainSaveRestoreFlags.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `0` - If the domain was saved.
+  /// * `null` - If there is an error.
+  ///
+  /// # Example (in JavaScript)
+  ///
+  /// ```javascript
+  /// machine.saveFlags('/var/lib/libvirt/save/vm.img', null,
+  ///   VirDomainSaveRestoreFlags.VirDomainSaveBypassCache);
+  /// Machine.domainRestoreFlags(conn, '/var/lib/libvirt/save/vm.img',
+  ///   VirDomainSaveRestoreFlags.VirDomainSaveRunning);
+  /// ```
+  #[napi]
+  pub fn save_flags(&self, to: String, dxml: Option<String>, flags: u32) -> Option<u32> {
+    let to = std::ffi::CString::new(to).ok()?;
+    let dxml = dxml.map(std::ffi::CString::new).transpose().ok()?;
+    let ret = unsafe {
+      virt::sys::virDomainSaveFlags(
+        self.domain.as_ptr(),
+        to.as_ptr(),
+        dxml.as_ref().map_or(std::ptr::null(), |dxml| dxml.as_ptr()),
+        flags,
+      )
+    };
+    if ret < 0 {
+      return None;
+    }
+    Some(0)
+  }
+
+  #[napi]
+  pub fn managed_save(&self, flags: u32) -> Option<u32> {
+    match self.domain.managed_save(flags) {
+      Ok(result) => Some(result),
+      Err(_) => None,
+    }
+  }
+
+  /// Same as `managedSave`, but runs on a worker thread and returns a Promise.
+  #[napi(ts_return_type = "Promise<number | null>")]
+  pub fn managed_save_async(&self, flags: u32) -> AsyncTask<crate::tasks::ManagedSaveTask> {
+    AsyncTask::new(crate::tasks::ManagedSaveTask::new(self.domain.clone(), flags))
+  }
+
+  #[napi]
+  pub fn has_managed_save(&self, flags: u32) -> Option<bool> {
+    match self.domain.has_managed_save(flags) {
+      Ok(result) => Some(result),
+      Err(_) => None,
+    }
+  }
+
+  #[napi]
+  pub fn managed_save_remove(&self, flags: u32) -> Option<u32> {
+    match self.domain.managed_save_remove(flags) {
+      Ok(result) => Some(result),
+      Err(_) => None,
+    }
+  }
+
+  /// Dump the guest memory to a file for analysis. See VirDomainCoreDumpFlags.
+  #[napi]
+  pub fn core_dump(&self, to: String, flags: u32) -> Option<u32> {
+    match self.domain.core_dump(&to, flags) {
+      Ok(result) => Some(result),
+      Err(_) => None,
+    }
+  }
+
+  /// Same as `coreDump`, but runs on a worker thread and returns a Promise.
+  #[napi(ts_return_type = "Promise<number | null>")]
+  pub fn core_dump_async(&self, to: String, flags: u32) -> AsyncTask<crate::tasks::CoreDumpTask> {
+    AsyncTask::new(crate::tasks::CoreDumpTask::new(self.domain.clone(), to, flags))
+  }
+
+  /// Dump the guest memory to a file in the given format. See
+  /// VirDomainCoreDumpFormat and VirDomainCoreDumpFlags.
+  #[napi]
+  pub fn core_dump_with_format(&self, to: String, format: u32, flags: u32) -> Option<u32> {
+    match self.domain.core_dump_with_format(&to, format, flags) {
+      Ok(result) => Some(result),
+      Err(_) => None,
+    }
+  }
+
+  #[napi]
+  pub fn set_metadata(
+    &self,
+    kind: i32,
+    metadata: String,
+    key: String,
+    uri: String,
+    flags: u32,
+  ) -> Option<u32> {
+    match self.domain.set_metadata(kind, Some(&metadata), Some(&key), Some(&uri), flags) {
+      Ok(result) => Some(result),
+      Err(_) => None,
+    }
+  }
+
+  #[napi]
+  pub fn get_metadata(&self, kind: i32, uri: String, flags: Option<u32>) -> Option<String> {
+    match self.domain.get_metadata(kind, Some(&uri), flags.unwrap_or(0)) {
+      Ok(result) => Some(result),
+      Err(_) => None,
+    }
+  }
+
+  #[napi]
+  pub fn block_resize(&self, disk: String, size: BigInt, flags: u32) -> Result<Option<u32>> {
+    let size_u64 = bigint_to_u64(&size)?;
+    match self.domain.block_resize(&disk, size_u64, flags) {
+      Ok(result) => Ok(Some(result)),
+      Err(_) => Ok(None),
+    }
+  }
+
+ #[napi]
+ pub fn get_memory_parameters(&self, flags: Option<u32>) -> Option<MemoryParameters> {
+    match self.domain.get_memory_parameters(flags.unwrap_or(0)) {
+      Ok(result) => Some(MemoryParameters {
+        hard_limit: result.hard_limit.map(|v| BigInt::from(v)),
+        soft_limit: result.soft_limit.map(|v| BigInt::from(v)),
+        min_guarantee: result.min_guarantee.map(|v| BigInt::from(v)),
+        swap_hard_limit: result.swap_hard_limit.map(|v| BigInt::from(v)),
+      }),
+      Err(_) => None,
+    }
+ }
+
+ #[napi]
+ pub fn set_memory_parameters(
+  &self,
+  params: crate::machine::MemoryParameters,
+  flags: u32,
+ ) -> Result<Option<u32>> {
+    let mem_param: virt::domain::MemoryParameters = virt::domain::MemoryParameters {
+      hard_limit: params.hard_limit.as_ref().map(bigint_to_u64).transpose()?,
+      soft_limit: params.soft_limit.as_ref().map(bigint_to_u64).transpose()?,
+      min_guarantee: params.min_guarantee.as_ref().map(bigint_to_u64).transpose()?,
+      swap_hard_limit: params.swap_hard_limit.as_ref().map(bigint_to_u64).transpose()?,
+    };
+    match self.domain.set_memory_parameters(mem_param, flags) {
+      Ok(result) => Ok(Some(result)),
+      Err(_) => Ok(None),
+    }
+  }
+
+  /// Get the block I/O weights of the domain.
+  ///
+  /// # Arguments
+  ///
+  /// * `flags` - See VirDomainModificationImpact.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `BlkioParameters` - The current weights.
+  /// * `null` - If there is an error while retrieving the parameters.
+  #[napi]
+  pub fn get_blkio_parameters(&self, flags: Option<u32>) -> Option<BlkioParameters> {
+    let dom = self.domain.as_ptr();
+    let flags = flags.unwrap_or(0);
+    let params = crate::typed_params::fetch(|params, nparams| unsafe {
+      virt::sys::virDomainGetBlkioParameters(dom, params, nparams, flags)
+    })?;
+    let weight = crate::typed_params::find(&params, "weight")
+      .and_then(|value| value.as_u64())
+      .map(|value| value as u32);
+    let device_weights = match crate::typed_params::find(&params, "device_weight") {
+      Some(crate::typed_params::TypedParamValue::String(value)) => Some(value.clone()),
+      _ => None,
+    };
+    Some(BlkioParameters { weight, device_weights })
+  }
+
+  /// Change the block I/O weights of the domain.
+  ///
+  /// # Arguments
+  ///
+  /// * `params` - The weights to change; fields left out are not changed.
+  /// * `flags` - See VirDomainModificationImpact.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `0` - If the weights were applied.
+  /// * `null` - If there is an error.
+  ///
+  /// # Example (in JavaScript)
+  ///
+  /// ```javascript
+  /// // Give the backup VM a quarter of the default weight on its disk.
+  /// backupVm.setBlkioParameters(
+  ///   { weight: 500, deviceWeights: '/dev/sda,125' },
+  ///   VirDomainModificationImpact.VirDomainAffectLive,
+  /// );
+  /// ```
+  #[napi]
+  pub fn set_blkio_parameters(&self, params: BlkioParameters, flags: u32) -> Option<u32> {
+    let mut typed = crate::typed_params::Builder::new();
+    if let Some(weight) = params.weight {
+      typed.add_uint("weight", weight)?;
+    }
+    if let Some(device_weights) = &params.device_weights {
+      typed.add_string("device_weight", device_weights)?;
+    }
+    let ret = unsafe {
+      virt::sys::virDomainSetBlkioParameters(self.domain.as_ptr(), typed.as_ptr(), typed.len(), flags)
+    };
+    if ret < 0 {
+      return None;
+    }
+    Some(0)
+  }
+
+  /// Get which perf events are enabled for the domain.
+  ///
+  /// # Arguments
+  ///
+  /// * `flags` - See VirDomainModificationImpact.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `PerfEvents` - Whether each event is enabled; events the host does not
+  ///   know about are left `undefined`.
+  /// * `null` - If there is an error.
+  #[napi]
+  pub fn get_perf_events(&self, flags: u32) -> Option<PerfEvents> {
+    let dom = self.domain.as_ptr();
+    let mut params: virt::sys::virTypedParameterPtr = std::ptr::null_mut();
+    let mut nparams: std::os::raw::c_int = 0;
+    if unsafe { virt::sys::virDomainGetPerfEvents(dom, &mut params, &mut nparams, flags) } < 0 {
+      return None;
+    }
+    let values = unsafe { crate::typed_params::from_raw(params, nparams) };
+    unsafe { virt::sys::virTypedParamsFree(params, nparams) };
+    let [
+      cmt,
+      mbmt,
+      mbml,
+      cpu_cycles,
+      instructions,
+      cache_references,
+      cache_misses,
+      branch_instructions,
+      branch_misses,
+      bus_cycles,
+      stalled_cycles_frontend,
+      stalled_cycles_backend,
+      ref_cpu_cycles,
+      cpu_clock,
+      task_clock,
+      page_faults,
+      context_switches,
+      cpu_migrations,
+      page_faults_min,
+      page_faults_maj,
+      alignment_faults,
+      emulation_faults,
+    ] = PERF_EVENT_FIELDS.map(|field| match crate::typed_params::find(&values, field) {
+      Some(crate::typed_params::TypedParamValue::Boolean(enabled)) => Some(*enabled),
+      _ => None,
+    });
+    Some(PerfEvents {
+      cmt,
+      mbmt,
+      mbml,
+      cpu_cycles,
+      instructions,
+      cache_references,
+      cache_misses,
+      branch_instructions,
+      branch_misses,
+      bus_cycles,
+      stalled_cycles_frontend,
+      stalled_cycles_backend,
+      ref_cpu_cycles,
+      cpu_clock,
+      task_clock,
+      page_faults,
+      context_switches,
+      cpu_migrations,
+      page_faults_min,
+      page_faults_maj,
+      alignment_faults,
+      emulation_faults,
+    })
+  }
+
+  /// Enable or disable perf events for the domain. The counters are then
+  /// reported in the `perf.*` fields of the PERF domain stats group.
+  ///
+  /// # Arguments
+  ///
+  /// * `params` - The events to change.
+  /// * `flags` - See VirDomainModificationImpact.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `0` - If the events were changed.
+  /// * `null` - If there is an error, e.g. the host kernel lacks an event.
+  ///
+  /// # Example (in JavaScript)
+  ///
+  /// ```javascript
+  /// machine.setPerfEvents(
+  ///   { cpuCycles: true, instructions: true, cacheMisses: true },
+  ///   VirDomainModificationImpact.VirDomainAffectLive,
+  /// );
+  /// const [{ stats }] = conn.getAllDomainStatsParsed(VirDomainStatsTypes.VirDomainStatsPerf, 0);
+  /// console.log(stats.perf.instructions / stats.perf.cpu_cycles);
+  /// ```
+  #[napi]
+  pub fn set_perf_events(&self, params: PerfEvents, flags: u32) -> Option<u32> {
+    let values = [
+      params.cmt,
+      params.mbmt,
+      params.mbml,
+      params.cpu_cycles,
+      params.instructions,
+      params.cache_references,
+      params.cache_misses,
+      params.branch_instructions,
+      params.branch_misses,
+      params.bus_cycles,
+      params.stalled_cycles_frontend,
+      params.stalled_cycles_backend,
+      params.ref_cpu_cycles,
+      params.cpu_clock,
+      params.task_clock,
+      params.page_faults,
+      params.context_switches,
+      params.cpu_migrations,
+      params.page_faults_min,
+      params.page_faults_maj,
+      params.alignment_faults,
+      params.emulation_faults,
+    ];
+    let mut typed = crate::typed_params::Builder::new();
+    for (field, value) in PERF_EVENT_FIELDS.iter().zip(values) {
+      if let Some(enabled) = value {
+        typed.add_boolean(field, enabled)?;
+      }
+    }
+    let ret = unsafe {
+      virt::sys::virDomainSetPerfEvents(self.domain.as_ptr(), typed.as_ptr(), typed.len(), flags)
+    };
+    if ret < 0 {
+      return None;
+    }
+    Some(0)
+  }
+
+  /// Get the progress of the job currently running on the domain.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `DomainJobInfo` - The job progress; `type` is `VirDomainJobNone` if no job is running.
+  /// * `null` - If there is an error.
+  ///
+  /// # Example (in JavaScript)
+  ///
+  /// ```javascript
+  /// const job = machine.getJobInfo();
+  /// if (job && job.type !== VirDomainJobType.VirDomainJobNone && job.dataTotal > 0n) {
+  ///   console.log(`migration ${job.dataProcessed * 100n / job.dataTotal}% done`);
+  /// }
+  /// ```
+  #[napi]
+  pub fn get_job_info(&self) -> Option<DomainJobInfo> {
+    let mut info: virt::sys::virDomainJobInfo = unsafe { std::mem::zeroed() };
+    if unsafe { virt::sys::virDomainGetJobInfo(self.domain.as_ptr(), &mut info) } < 0 {
+      return None;
+    }
+    Some(DomainJobInfo {
+      kind: info.type_,
+      time_elapsed: info.timeElapsed.into(),
+      time_remaining: info.timeRemaining.into(),
+      data_total: info.dataTotal.into(),
+      data_processed: info.dataProcessed.into(),
+      data_remaining: info.dataRemaining.into(),
+      mem_total: info.memTotal.into(),
+      mem_processed: info.memProcessed.into(),
+      mem_remaining: info.memRemaining.into(),
+    })
+  }
+
+  /// Abort the job currently running on the domain, e.g. a stuck migration.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `0` - If the job was aborted.
+  /// * `null` - If no job is running or it cannot be aborted.
+  #[napi]
+  pub fn abort_job(&self) -> Option<u32> {
+    match unsafe { virt::sys::virDomainAbortJob(self.domain.as_ptr()) } {
+      -1 => None,
+      _ => Some(0),
+    }
+  }
+
+  /// Start copying a disk of the running domain to a new destination.
+  ///
+  /// # Arguments
+  ///
+  /// * `disk` - The disk target, e.g. `vda`.
+  /// * `destxml` - A `<disk>` element describing the destination.
+  /// * `flags` - Bitwise-OR of virDomainBlockCopyFlags.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `0` - If the copy job started. Follow it with `blockJobInfo` and end it
+  ///   with `blockJobAbort`, optionally pivoting to the copy.
+  /// * `null` - If there is an error.
+  #[napi]
+  pub fn block_copy(&self, disk: String, destxml: String, flags: u32) -> Option<u32> {
+    let disk = std::ffi::CString::new(disk).ok()?;
+    let destxml = std::ffi::CString::new(destxml).ok()?;
+    let ret = unsafe {
+      virt::sys::virDomainBlockCopy(
+        self.domain.as_ptr(),
+        disk.as_ptr(),
+        destxml.as_ptr(),
+        std::ptr::null_mut(),
+        0,
+        flags,
+      )
+    };
+    if ret < 0 {
+      return None;
+    }
+    Some(0)
+  }
+
+  /// Start merging a range of the backing chain of a disk into a lower image.
+  ///
+  /// # Arguments
+  ///
+  /// * `disk` - The disk target, e.g. `vda`.
+  /// * `base` - The image to commit into, or `null` for the deepest backing file.
+  /// * `top` - The topmost image to commit, or `null` for the active layer.
+  /// * `bandwidth` - Bandwidth limit in MiB/s, `0n` for unlimited.
+  /// * `flags` - Bitwise-OR of VirDomainBlockCommitFlags.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `0` - If the commit job started.
+  /// * `null` - If there is an error.
+  ///
+  /// # Example (in JavaScript)
+  ///
+  /// ```javascript
+  /// // Merge the active overlay of an external snapshot back into its base
+  /// // image, then switch to the base.
+  /// machine.blockCommit('vda', null, null, 0n, VirDomainBlockCommitFlags.VirDomainBlockCommitActive);
+  /// const timer = setInterval(() => {
+  ///   const job = machine.blockJobInfo('vda', 0);
+  ///   if (job && job.cur === job.end) {
+  ///     clearInterval(timer);
+  ///     machine.blockJobAbort('vda', VirDomainBlockJobAbortFlags.VirDomainBlockJobAbortPivot);
+  ///   }
+  /// }, 500);
+  /// ```
+  #[napi]
+  pub fn block_commit(
+    &self,
+    disk: String,
+    base: Option<String>,
+    top: Option<String>,
+    bandwidth: BigInt,
+    flags: u32,
+  ) -> Result<Option<u32>> {
+    let bandwidth = bigint_to_u64(&bandwidth)?;
+    let (disk, base, top) = match (
+      std::ffi::CString::new(disk),
+      base.map(std::ffi::CString::new).transpose(),
+      top.map(std::ffi::CString::new).transpose(),
+    ) {
+      (Ok(disk), Ok(base), Ok(top)) => (disk, base, top),
+      _ => return Ok(None),
+    };
+    let ret = unsafe {
+      virt::sys::virDomainBlockCommit(
+        self.domain.as_ptr(),
+        disk.as_ptr(),
+        base.as_ref().map_or(std::ptr::null(), |base| base.as_ptr()),
+        top.as_ref().map_or(std::ptr::null(), |top| top.as_ptr()),
+        bandwidth as std::os::raw::c_ulong,
+        flags,
+      )
+    };
+    if ret < 0 {
+      return Ok(None);
+    }
+    Ok(Some(0))
+  }
+
+  /// Start populating a disk image with data from its backing chain.
+  ///
+  /// # Arguments
+  ///
+  /// * `disk` - The disk target, e.g. `vda`.
+  /// * `bandwidth` - Bandwidth limit in MiB/s, `0n` for unlimited.
+  /// * `flags` - Bitwise-OR of virDomainBlockPullFlags.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `0` - If the pull job started.
+  /// * `null` - If there is an error.
+  #[napi]
+  pub fn block_pull(&self, disk: String, bandwidth: BigInt, flags: u32) -> Result<Option<u32>> {
+    let bandwidth = bigint_to_u64(&bandwidth)?;
+    let disk = match std::ffi::CString::new(disk) {
+      Ok(disk) => disk,
+      Err(_) => return Ok(None),
+    };
+    let ret = unsafe {
+      virt::sys::virDomainBlockPull(
+        self.domain.as_ptr(),
+        disk.as_ptr(),
+        bandwidth as std::os::raw::c_ulong,
+        flags,
+      )
+    };
+    if ret < 0 {
+      return Ok(None);
+    }
+    Ok(Some(0))
+  }
+
+  /// Cancel the block job running on a disk, or finish a copy or active
+  /// commit job by pivoting to the new image.
+  ///
+  /// # Arguments
+  ///
+  /// * `disk` - The disk target, e.g. `vda`.
+  /// * `flags` - See VirDomainBlockJobAbortFlags.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `0` - If the job was aborted or pivoted.
+  /// * `null` - If there is an error.
+  #[napi]
+  pub fn block_job_abort(&self, disk: String, flags: u32) -> Option<u32> {
+    let disk = std::ffi::CString::new(disk).ok()?;
+    match unsafe { virt::sys::virDomainBlockJobAbort(self.domain.as_ptr(), disk.as_ptr(), flags) } {
+      -1 => None,
+      _ => Some(0),
+    }
+  }
+
+  /// Get the progress of the block job running on a disk.
+  ///
+  /// # Arguments
+  ///
+  /// * `disk` - The disk target, e.g. `vda`.
+  /// * `flags` - Bitwise-OR of virDomainBlockJobInfoFlags.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `BlockJobInfo` - The job progress.
+  /// * `null` - If no job is running on the disk or there is an error.
+  #[napi]
+  pub fn block_job_info(&self, disk: String, flags: u32) -> Option<BlockJobInfo> {
+    let disk = std::ffi::CString::new(disk).ok()?;
+    let mut info: virt::sys::virDomainBlockJobInfo = unsafe { std::mem::zeroed() };
+    let ret = unsafe {
+      virt::sys::virDomainGetBlockJobInfo(self.domain.as_ptr(), disk.as_ptr(), &mut info, flags)
+    };
+    if ret <= 0 {
+      return None;
+    }
+    Some(BlockJobInfo {
+      kind: info.type_,
+      bandwidth: (info.bandwidth as u64).into(),
+      cur: info.cur.into(),
+      end: info.end.into(),
+    })
+  }
+
+  /// Get information about the guest OS from the guest agent in a single call.
+  ///
+  /// # Arguments
+  ///
+  /// * `types` - Bitwise-OR of VirDomainGuestInfoTypes, or `0` for everything.
+  /// * `flags` - Currently unused, pass 0.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * An object nested on the libvirt parameter names, e.g.
+  ///   `{ os: { id: 'fedora' }, hostname: 'vm1', fs: { count: 1, 0: { mountpoint: '/' } } }`.
+  /// * `null` - If the guest agent is not reachable or there is an error.
+  ///
+  /// # Example (in JavaScript)
+  ///
+  /// ```javascript
+  /// const info = machine.getGuestInfo(
+  ///   VirDomainGuestInfoTypes.VirDomainGuestInfoOs | VirDomainGuestInfoTypes.VirDomainGuestInfoFilesystem, 0);
+  /// console.log(info.os['pretty-name']);
+  /// for (let i = 0; i < info.fs.count; i++) {
+  ///   console.log(info.fs[i].mountpoint, info.fs[i]['used-bytes']);
+  /// }
+  /// ```
+  #[napi]
+  pub fn get_guest_info(&self, types: u32, flags: u32) -> Option<serde_json::Value> {
+    let mut params: virt::sys::virTypedParameterPtr = std::ptr::null_mut();
+    let mut nparams: std::os::raw::c_int = 0;
+    let ret = unsafe {
+      virt::sys::virDomainGetGuestInfo(self.domain.as_ptr(), types, &mut params, &mut nparams, flags)
+    };
+    if ret < 0 {
+      return None;
+    }
+    let values = unsafe { crate::typed_params::from_raw(params, nparams) };
+    unsafe { virt::sys::virTypedParamsFree(params, nparams) };
+    Some(crate::typed_params::to_nested_json(&values))
+  }
+
+  /// Freeze guest filesystems through the guest agent, e.g. right before a snapshot.
+  ///
+  /// # Arguments
+  ///
+  /// * `mountpoints` - The mount points to freeze, or `null` for all of them.
+  /// * `flags` - Currently unused, pass 0.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `number` - The number of frozen filesystems.
+  /// * `null` - If the guest agent is not reachable or there is an error.
+  ///
+  /// # Example (in JavaScript)
+  ///
+  /// ```javascript
+  /// machine.fsFreeze(null, 0);
+  /// try {
+  ///   machine.createExternalSnapshot('backup', [{ name: 'vda', file: '/var/lib/libvirt/images/vm.overlay.qcow2' }], 0);
+  /// } finally {
+  ///   machine.fsThaw(null, 0);
+  /// }
+  /// ```
+  #[napi]
+  pub fn fs_freeze(&self, mountpoints: Option<Vec<String>>, flags: u32) -> Option<u32> {
+    let mountpoints = mountpoints
+      .unwrap_or_default()
+      .into_iter()
+      .map(std::ffi::CString::new)
+      .collect::<std::result::Result<Vec<_>, _>>()
+      .ok()?;
+    let mut ptrs: Vec<*const std::os::raw::c_char> = mountpoints.iter().map(|mountpoint| mountpoint.as_ptr()).collect();
+    let ret = unsafe {
+      virt::sys::virDomainFSFreeze(
+        self.domain.as_ptr(),
+        if ptrs.is_empty() { std::ptr::null_mut() } else { ptrs.as_mut_ptr() },
+        ptrs.len() as u32,
+        flags,
+      )
+    };
+    if ret < 0 {
+      return None;
+    }
+    Some(ret as u32)
+  }
+
+  /// Thaw guest filesystems frozen with `fsFreeze`.
+  ///
+  /// # Arguments
+  ///
+  /// * `mountpoints` - The mount points to thaw, or `null` for all of them.
+  /// * `flags` - Currently unused, pass 0.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `number` - The number of thawed filesystems.
+  /// * `null` - If the guest agent is not reachable or there is an error.
+  #[napi]
+  pub fn fs_thaw(&self, mountpoints: Option<Vec<String>>, flags: u32) -> Option<u32> {
+    let mountpoints = mountpoints
+      .unwrap_or_default()
+      .into_iter()
+      .map(std::ffi::CString::new)
+      .collect::<std::result::Result<Vec<_>, _>>()
+      .ok()?;
+    let mut ptrs: Vec<*const std::os::raw::c_char> = mountpoints.iter().map(|mountpoint| mountpoint.as_ptr()).collect();
+    let ret = unsafe {
+      virt::sys::virDomainFSThaw(
+        self.domain.as_ptr(),
+        if ptrs.is_empty() { std::ptr::null_mut() } else { ptrs.as_mut_ptr() },
+        ptrs.len() as u32,
+        flags,
+      )
+    };
+    if ret < 0 {
+      return None;
+    }
+    Some(ret as u32)
+  }
+
+  /// Discard unused blocks of guest filesystems through the guest agent, so
+  /// thin provisioned disks give the space back to the host.
+  ///
+  /// # Arguments
+  ///
+  /// * `mountpoint` - The mount point to trim, or `null` for all filesystems.
+  /// * `minimum` - Smallest contiguous free range to discard, in bytes. Pass `0n`
+  ///   to discard every free block.
+  /// * `flags` - Currently unused, pass 0.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `0` - If the filesystems were trimmed.
+  /// * `null` - If the guest agent is not reachable or there is an error.
+  #[napi]
+  pub fn fs_trim(&self, mountpoint: Option<String>, minimum: BigInt, flags: u32) -> Result<Option<u32>> {
+    let minimum = bigint_to_u64(&minimum)?;
+    let mountpoint = match mountpoint.map(std::ffi::CString::new).transpose() {
+      Ok(mountpoint) => mountpoint,
+      Err(_) => return Ok(None),
+    };
+    let ret = unsafe {
+      virt::sys::virDomainFSTrim(
+        self.domain.as_ptr(),
+        mountpoint.as_ref().map_or(std::ptr::null(), |mountpoint| mountpoint.as_ptr()),
+        minimum,
+        flags,
+      )
+    };
+    if ret < 0 {
+      return Ok(None);
+    }
+    Ok(Some(0))
+  }
+
+  /// Send key presses to the guest, as if typed on its keyboard.
+  ///
+  /// # Arguments
+  ///
+  /// * `codeset` - The code set of `keycodes`. See VirKeycodeSet.
+  /// * `holdtime` - How long to hold the keys down, in milliseconds (0 for the default).
+  /// * `keycodes` - The keys to press together, at most 16.
+  /// * `flags` - Currently unused, pass 0.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `0` - If the keys were sent.
+  /// * `null` - If there is an error.
+  ///
+  /// # Example (in JavaScript)
+  ///
+  /// ```javascript
+  /// // Ctrl-Alt-Del (KEY_LEFTCTRL, KEY_LEFTALT, KEY_DELETE)
+  /// machine.sendKey(VirKeycodeSet.VirKeycodeSetLinux, 0, [29, 56, 111], 0);
+  /// ```
+  #[napi]
+  pub fn send_key(&self, codeset: u32, holdtime: u32, keycodes: Vec<u32>, flags: u32) -> Option<u32> {
+    let mut keycodes = keycodes;
+    let ret = unsafe {
+      virt::sys::virDomainSendKey(
+        self.domain.as_ptr(),
+        codeset,
+        holdtime,
+        keycodes.as_mut_ptr(),
+        keycodes.len() as std::os::raw::c_int,
+        flags,
+      )
+    };
+    if ret < 0 {
+      return None;
+    }
+    Some(0)
+  }
+
+  /// Take a screenshot of a display of the domain.
+  ///
+  /// # Arguments
+  ///
+  /// * `screen` - The display to capture, `0` for the first one.
+  /// * `flags` - Currently unused, pass 0.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `Screenshot` - The image and its mime type.
+  /// * `null` - If the domain has no display or there is an error.
+  ///
+  /// # Example (in JavaScript)
+  ///
+  /// ```javascript
+  /// const shot = machine.screenshot(0, 0);
+  /// if (shot) {
+  ///   const ext = shot.mime === 'image/png' ? 'png' : 'ppm';
+  ///   fs.writeFileSync(`thumbnail.${ext}`, shot.data);
+  /// }
+  /// ```
+  #[napi]
+  pub fn screenshot(&self, screen: u32, flags: u32) -> Option<Screenshot> {
+    let dom = self.domain.as_ptr();
+    let stream = crate::stream::Stream::on_connection(unsafe { virt::sys::virDomainGetConnect(dom) }, 0)?;
+    let mime = unsafe { virt::sys::virDomainScreenshot(dom, stream.get().as_ptr(), screen, flags) };
+    if mime.is_null() {
+      return None;
+    }
+    let mime_type = unsafe { std::ffi::CStr::from_ptr(mime) }.to_string_lossy().into_owned();
+    unsafe { free_libvirt_memory(mime) };
+    Some(Screenshot {
+      mime: mime_type,
+      data: stream.drain()?.into(),
+    })
+  }
+
+  /// Get the security label of the domain process.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `SecurityLabel` - The label and whether it is enforced.
+  /// * `null` - If there is an error.
+  #[napi]
+  pub fn get_security_label(&self) -> Option<SecurityLabel> {
+    let mut seclabel: virt::sys::virSecurityLabel = unsafe { std::mem::zeroed() };
+    if unsafe { virt::sys::virDomainGetSecurityLabel(self.domain.as_ptr(), &mut seclabel) } < 0 {
+      return None;
+    }
+    Some(SecurityLabel {
+      label: unsafe { std::ffi::CStr::from_ptr(seclabel.label.as_ptr()) }
+        .to_string_lossy()
+        .into_owned(),
+      enforcing: seclabel.enforcing != 0,
+    })
+  }
+
+  /// Get the state of the control interface to the hypervisor, e.g. whether
+  /// the QEMU monitor is busy with a job. This call never blocks, so it is safe
+  /// to use from a watchdog even when other calls on the domain hang.
+  ///
+  /// # Arguments
+  ///
+  /// * `flags` - Currently unused, pass 0.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `DomainControlInfo` - The control state.
+  /// * `null` - If there is an error.
+  ///
+  /// # Example (in JavaScript)
+  ///
+  /// ```javascript
+  /// const control = machine.getControlInfo(0);
+  /// if (control.state === VirDomainControlState.VirDomainControlOccupied && control.stateTime > 60000n) {
+  ///   console.warn('monitor busy for over a minute');
+  /// }
+  /// ```
   #[napi]
-  pub fn update_device_flags(&self, xml: String, flags: u32) -> Option<u32> {
-    match self.domain.update_device_flags(&xml, flags) {
-      Ok(result) => Some(result),
-      Err(_) => None,
+  pub fn get_control_info(&self, flags: u32) -> Option<DomainControlInfo> {
+    let mut info: virt::sys::virDomainControlInfo = unsafe { std::mem::zeroed() };
+    if unsafe { virt::sys::virDomainGetControlInfo(self.domain.as_ptr(), &mut info, flags) } < 0 {
+      return None;
     }
+    Some(DomainControlInfo {
+      state: info.state,
+      details: info.details,
+      state_time: info.stateTime.into(),
+    })
   }
 
+  /// Get the disks that hit an I/O error, e.g. because their storage ran out
+  /// of space or went read-only. This is where to look when `getState` reports
+  /// the domain paused with reason `VIR_DOMAIN_PAUSED_IOERROR`.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `DiskError[]` - The disks with an error, empty if there is none.
+  /// * `null` - If there is an error.
+  ///
+  /// # Example (in JavaScript)
+  ///
+  /// ```javascript
+  /// for (const { disk, error } of machine.getDiskErrors()) {
+  ///   if (error === VirDomainDiskErrorCode.VirDomainDiskErrorNoSpace) {
+  ///     console.warn(`${disk} is out of space`);
+  ///   }
+  /// }
+  /// ```
   #[napi]
-  pub fn managed_save(&self, flags: u32) -> Option<u32> {
-    match self.domain.managed_save(flags) {
-      Ok(result) => Some(result),
-      Err(_) => None,
+  pub fn get_disk_errors(&self) -> Option<Vec<DiskError>> {
+    let count = unsafe {
+      virt::sys::virDomainGetDiskErrors(self.domain.as_ptr(), std::ptr::null_mut(), 0, 0)
+    };
+    if count < 0 {
+      return None;
+    }
+    let mut errors: Vec<virt::sys::virDomainDiskError> =
+      (0..count).map(|_| unsafe { std::mem::zeroed() }).collect();
+    let count = unsafe {
+      virt::sys::virDomainGetDiskErrors(self.domain.as_ptr(), errors.as_mut_ptr(), count as u32, 0)
+    };
+    if count < 0 {
+      return None;
     }
+    Some(
+      errors
+        .into_iter()
+        .take(count as usize)
+        .map(|entry| unsafe {
+          let disk = std::ffi::CStr::from_ptr(entry.disk).to_string_lossy().into_owned();
+          free_libvirt_memory(entry.disk);
+          DiskError {
+            disk,
+            error: entry.error as u32,
+          }
+        })
+        .collect(),
+    )
   }
 
+  /// Ask the guest to suspend itself through the guest agent.
+  ///
+  /// # Arguments
+  ///
+  /// * `target` - Where to suspend to. See VirNodeSuspendTarget.
+  /// * `duration` - Seconds until the guest wakes up again; must be `0n` (no
+  ///   automatic wakeup) with current hypervisors.
+  /// * `flags` - Currently unused, pass 0.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `0` - If the guest was suspended. The domain state becomes `VirDomainPmsuspended`.
+  /// * `null` - If the guest agent is not reachable or there is an error.
   #[napi]
-  pub fn has_managed_save(&self, flags: u32) -> Option<bool> {
-    match self.domain.has_managed_save(flags) {
-      Ok(result) => Some(result),
-      Err(_) => None,
+  pub fn pm_suspend_for_duration(&self, target: u32, duration: BigInt, flags: u32) -> Result<Option<u32>> {
+    let duration = bigint_to_u64(&duration)?;
+    let ret = unsafe {
+      virt::sys::virDomainPMSuspendForDuration(self.domain.as_ptr(), target, duration, flags)
+    };
+    if ret < 0 {
+      return Ok(None);
     }
+    Ok(Some(0))
   }
 
+  /// Wake up a guest suspended with `pmSuspendForDuration`.
+  ///
+  /// # Arguments
+  ///
+  /// * `flags` - Currently unused, pass 0.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `0` - If the guest was woken up.
+  /// * `null` - If there is an error.
   #[napi]
-  pub fn managed_save_remove(&self, flags: u32) -> Option<u32> {
-    match self.domain.managed_save_remove(flags) {
-      Ok(result) => Some(result),
-      Err(_) => None,
+  pub fn pm_wakeup(&self, flags: u32) -> Option<u32> {
+    match unsafe { virt::sys::virDomainPMWakeup(self.domain.as_ptr(), flags) } {
+      -1 => None,
+      _ => Some(0),
     }
   }
 
+  /// Send a non-maskable interrupt to the guest, e.g. to trigger a kdump or a
+  /// Windows crash dump on a hung guest.
+  ///
+  /// # Arguments
+  ///
+  /// * `flags` - Currently unused, pass 0.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `0` - If the NMI was injected.
+  /// * `null` - If there is an error.
   #[napi]
-  pub fn core_dump(&self, to: String, flags: u32) -> Option<u32> {
-    match self.domain.core_dump(&to, flags) {
-      Ok(result) => Some(result),
-      Err(_) => None,
+  pub fn inject_nmi(&self, flags: u32) -> Option<u32> {
+    match unsafe { virt::sys::virDomainInjectNMI(self.domain.as_ptr(), flags) } {
+      -1 => None,
+      _ => Some(0),
     }
   }
 
+  /// Get the I/O limits of a disk of the domain.
+  ///
+  /// # Arguments
+  ///
+  /// * `disk` - The disk target, e.g. `vda`, or the path of its source.
+  /// * `flags` - See VirDomainModificationImpact.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `BlockIoTune` - The current limits, `0` meaning unlimited.
+  /// * `null` - If the disk does not exist or there is an error.
   #[napi]
-  pub fn core_dump_with_format(&self, to: String, format: u32, flags: u32) -> Option<u32> {
-    match self.domain.core_dump_with_format(&to, format, flags) {
-      Ok(result) => Some(result),
-      Err(_) => None,
-    }
+  pub fn get_block_io_tune(&self, disk: String, flags: Option<u32>) -> Option<BlockIoTune> {
+    let dom = self.domain.as_ptr();
+    let disk = std::ffi::CString::new(disk).ok()?;
+    let flags = flags.unwrap_or(0);
+    let params = crate::typed_params::fetch(|params, nparams| unsafe {
+      virt::sys::virDomainGetBlockIoTune(dom, disk.as_ptr(), params, nparams, flags)
+    })?;
+    let [total_bytes_sec, read_bytes_sec, write_bytes_sec, total_iops_sec, read_iops_sec, write_iops_sec] =
+      BLOCK_IO_TUNE_FIELDS.map(|field| {
+        crate::typed_params::find(&params, field)
+          .and_then(|value| value.as_u64())
+          .map(BigInt::from)
+      });
+    Some(BlockIoTune {
+      total_bytes_sec,
+      read_bytes_sec,
+      write_bytes_sec,
+      total_iops_sec,
+      read_iops_sec,
+      write_iops_sec,
+    })
   }
 
+  /// Change the I/O limits of a disk of the running domain.
+  ///
+  /// # Arguments
+  ///
+  /// * `disk` - The disk target, e.g. `vda`, or the path of its source.
+  /// * `params` - The limits to change.
+  /// * `flags` - See VirDomainModificationImpact.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `0` - If the limits were applied.
+  /// * `null` - If there is an error.
+  ///
+  /// # Example (in JavaScript)
+  ///
+  /// ```javascript
+  /// machine.setBlockIoTune('vda', { totalIopsSec: 500n, totalBytesSec: 50n * 1024n * 1024n },
+  ///   VirDomainModificationImpact.VirDomainAffectLive);
+  /// ```
   #[napi]
-  pub fn set_metadata(
-    &self,
-    kind: i32,
-    metadata: String,
-    key: String,
-    uri: String,
-    flags: u32,
-  ) -> Option<u32> {
-    match self.domain.set_metadata(kind, Some(&metadata), Some(&key), Some(&uri), flags) {
-      Ok(result) => Some(result),
-      Err(_) => None,
+  pub fn set_block_io_tune(&self, disk: String, params: BlockIoTune, flags: u32) -> Result<Option<u32>> {
+    let values = [
+      &params.total_bytes_sec,
+      &params.read_bytes_sec,
+      &params.write_bytes_sec,
+      &params.total_iops_sec,
+      &params.read_iops_sec,
+      &params.write_iops_sec,
+    ];
+    let mut typed = crate::typed_params::Builder::new();
+    for (field, value) in BLOCK_IO_TUNE_FIELDS.iter().zip(values) {
+      if let Some(value) = value {
+        if typed.add_ullong(field, bigint_to_u64(value)?).is_none() {
+          return Ok(None);
+        }
+      }
+    }
+    let disk = match std::ffi::CString::new(disk) {
+      Ok(disk) => disk,
+      Err(_) => return Ok(None),
+    };
+    let ret = unsafe {
+      virt::sys::virDomainSetBlockIoTune(self.domain.as_ptr(), disk.as_ptr(), typed.as_ptr(), typed.len(), flags)
+    };
+    if ret < 0 {
+      return Ok(None);
     }
+    Ok(Some(0))
   }
 
+  /// Get the CPU scheduler parameters of the domain.
+  ///
+  /// # Arguments
+  ///
+  /// * `flags` - See VirDomainModificationImpact.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * An object keyed by libvirt parameter name, e.g.
+  ///   `{ cpu_shares: 1024, vcpu_period: 100000, vcpu_quota: -1, ... }`.
+  ///   The available parameters depend on the hypervisor.
+  /// * `null` - If there is an error while retrieving the parameters.
   #[napi]
-  pub fn get_metadata(&self, kind: i32, uri: String, flags: u32) -> Option<String> {
-    match self.domain.get_metadata(kind, Some(&uri), flags) {
-      Ok(result) => Some(result),
-      Err(_) => None,
-    }
+  pub fn get_scheduler_parameters(&self, flags: u32) -> Option<serde_json::Value> {
+    let dom = self.domain.as_ptr();
+    let params = crate::typed_params::fetch(|params, nparams| unsafe {
+      if params.is_null() {
+        // The scheduler type call is the only way to learn the parameter count.
+        let kind = virt::sys::virDomainGetSchedulerType(dom, nparams);
+        if kind.is_null() {
+          return -1;
+        }
+        free_libvirt_memory(kind);
+        0
+      } else {
+        virt::sys::virDomainGetSchedulerParametersFlags(dom, params, nparams, flags)
+      }
+    })?;
+    Some(serde_json::Value::Object(
+      params
+        .iter()
+        .map(|(field, value)| (field.clone(), value.to_json()))
+        .collect(),
+    ))
   }
 
+  /// Change the CPU scheduler parameters of the domain.
+  ///
+  /// # Arguments
+  ///
+  /// * `params` - The parameters to change.
+  /// * `flags` - See VirDomainModificationImpact.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `0` - If the parameters were applied.
+  /// * `null` - If there is an error.
+  ///
+  /// # Example (in JavaScript)
+  ///
+  /// ```javascript
+  /// // Cap every vCPU to half a host CPU.
+  /// machine.setSchedulerParameters(
+  ///   { vcpuPeriod: 100000n, vcpuQuota: 50000n },
+  ///   VirDomainModificationImpact.VirDomainAffectLive,
+  /// );
+  /// ```
   #[napi]
-  pub fn block_resize(&self, disk: String, size: BigInt, flags: u32) -> Option<u32> {
-    let (_signed, size_u64, lossless) = size.get_u64();
-    if !lossless {
-      return None;
-    }
-    match self.domain.block_resize(&disk, size_u64, flags) {
-      Ok(result) => Some(result),
-      Err(_) => None,
-    }
-  }
+  pub fn set_scheduler_parameters(&self, params: SchedulerParameters, flags: u32) -> Result<Option<u32>> {
+    let cpu_shares = params.cpu_shares.as_ref().map(bigint_to_u64).transpose()?;
+    let vcpu_period = params.vcpu_period.as_ref().map(bigint_to_u64).transpose()?;
+    let vcpu_quota = params.vcpu_quota.as_ref().map(bigint_to_i64).transpose()?;
+    let emulator_period = params.emulator_period.as_ref().map(bigint_to_u64).transpose()?;
+    let emulator_quota = params.emulator_quota.as_ref().map(bigint_to_i64).transpose()?;
 
- #[napi]
- pub fn get_memory_parameters(&self, flags: u32) -> Option<MemoryParameters> {
-    match self.domain.get_memory_parameters(flags) {
-      Ok(result) => Some(MemoryParameters {
-        hard_limit: result.hard_limit.map(|v| BigInt::from(v)),
-        soft_limit: result.soft_limit.map(|v| BigInt::from(v)),
-        min_guarantee: result.min_guarantee.map(|v| BigInt::from(v)),
-        swap_hard_limit: result.swap_hard_limit.map(|v| BigInt::from(v)),
-      }),
-      Err(_) => None,
+    let mut typed = crate::typed_params::Builder::new();
+    let built = (|| {
+      if let Some(value) = cpu_shares {
+        typed.add_ullong("cpu_shares", value)?;
+      }
+      if let Some(value) = vcpu_period {
+        typed.add_ullong("vcpu_period", value)?;
+      }
+      if let Some(value) = vcpu_quota {
+        typed.add_llong("vcpu_quota", value)?;
+      }
+      if let Some(value) = emulator_period {
+        typed.add_ullong("emulator_period", value)?;
+      }
+      if let Some(value) = emulator_quota {
+        typed.add_llong("emulator_quota", value)?;
+      }
+      Some(())
+    })();
+    if built.is_none() {
+      return Ok(None);
     }
- }
-
- #[napi]
- pub fn set_memory_parameters(
-  &self,
-  params: crate::machine::MemoryParameters,
-  flags: u32,
- ) -> Option<u32> {
-    // TODO: Check params overflow, it should be u64 but BigInt is used because u64 is not supported by N-API
-    let mem_param: virt::domain::MemoryParameters = virt::domain::MemoryParameters {
-      hard_limit: params.hard_limit.map(|v| v.get_u64().1),
-      soft_limit: params.soft_limit.map(|v| v.get_u64().1),
-      min_guarantee: params.min_guarantee.map(|v| v.get_u64().1),
-      swap_hard_limit: params.swap_hard_limit.map(|v| v.get_u64().1),
+    let ret = unsafe {
+      virt::sys::virDomainSetSchedulerParametersFlags(self.domain.as_ptr(), typed.as_ptr(), typed.len(), flags)
     };
-    match self.domain.set_memory_parameters(mem_param, flags) {
-      Ok(result) => Some(result),
-      Err(_) => None,
+    if ret < 0 {
+      return Ok(None);
     }
+    Ok(Some(0))
   }
 
   #[napi]
@@ -1527,17 +3965,37 @@ impl Machine {
     flags: u32,
     uri: String,
     bandwidth: BigInt,
-  ) -> Option<Machine> {
-    let (_signed, bandwidth_u64, lossless) = bandwidth.get_u64();
-    if !lossless {
-      return None;
-    }
+  ) -> Result<Option<Machine>> {
+    let bandwidth_u64 = bigint_to_u64(&bandwidth)?;
     match self.domain.migrate(dconn.get_connection(), flags, None, Some(&uri), bandwidth_u64) {
-      Ok(result) => Some(Machine::from_domain(result, &dconn)),
-      Err(_) => None,
+      Ok(result) => Ok(Some(Machine::from_domain(result, &dconn))),
+      Err(_) => Ok(None),
     }
   }
 
+  /// Same as `migrate`, but runs on a worker thread and returns a Promise,
+  /// so a migration taking minutes does not freeze the Node.js event loop.
+  ///
+  /// Throws right away if `bandwidth` does not fit in an unsigned 64-bit
+  /// integer; the Promise resolves to `null` if the migration fails.
+  #[napi(ts_return_type = "Promise<Machine | null>")]
+  pub fn migrate_async(
+    &self,
+    dconn: &Connection,
+    flags: u32,
+    uri: String,
+    bandwidth: BigInt,
+  ) -> Result<AsyncTask<crate::tasks::MigrateTask>> {
+    let bandwidth_u64 = bigint_to_u64(&bandwidth)?;
+    Ok(AsyncTask::new(crate::tasks::MigrateTask::new(
+      self.domain.clone(),
+      dconn.share(),
+      flags,
+      uri,
+      bandwidth_u64,
+    )))
+  }
+
   // Renamed, originally called migrate2
   #[napi]
   pub fn migrate_with_xml(
@@ -1547,26 +4005,45 @@ impl Machine {
     flags: u32,
     uri: String,
     bandwidth: BigInt,
-  ) -> Option<Machine> {
-    let (_signed, bandwidth_u64, lossless) = bandwidth.get_u64();
-    if !lossless {
-      return None;
-    }
+  ) -> Result<Option<Machine>> {
+    let bandwidth_u64 = bigint_to_u64(&bandwidth)?;
     match self.domain.migrate2(dconn.get_connection(), Some(&dxml), flags, None, Some(&uri), bandwidth_u64) {
-      Ok(result) => Some(Machine::from_domain(result, &dconn)),
-      Err(_) => None,
+      Ok(result) => Ok(Some(Machine::from_domain(result, &dconn))),
+      Err(_) => Ok(None),
     }
   }
 
+  /// Migrate the domain to the host at `uri`, without a connection to the destination.
+  ///
+  /// # Arguments
+  ///
+  /// * `uri` - With `VirDomainMigratePeer2peer`, the libvirt URI of the destination
+  ///   (e.g. `qemu+ssh://host/system`); otherwise the hypervisor migration URI
+  ///   (e.g. `tcp://host:49152`).
+  /// * `flags` - See VirDomainMigrateFlags.
+  /// * `bandwidth` - Bandwidth limit in MiB/s, `0n` for unlimited.
+  /// * `dname` - New name of the domain on the destination, or `null` to keep it.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `0` - If the domain was migrated.
+  /// * `null` - If the migration failed.
+  ///
+  /// Throws if `uri` has no scheme or `bandwidth` is out of range.
   #[napi]
-  pub fn migrate_to_uri(&self, uri: String, flags: u32, bandwidth: BigInt) -> Option<u32> {
-    let (_signed, bandwidth_u64, lossless) = bandwidth.get_u64();
-    if !lossless {
-      return None;
-    }
-    match self.domain.migrate_to_uri(&uri, flags, Some(""), bandwidth_u64) {
-      Ok(_) => Some(0),
-      Err(_) => None,
+  pub fn migrate_to_uri(
+    &self,
+    uri: String,
+    flags: u32,
+    bandwidth: BigInt,
+    dname: Option<String>,
+  ) -> Result<Option<u32>> {
+    validate_migrate_uri(&uri)?;
+    let bandwidth_u64 = bigint_to_u64(&bandwidth)?;
+    match self.domain.migrate_to_uri(&uri, flags, dname.as_deref(), bandwidth_u64) {
+      Ok(_) => Ok(Some(0)),
+      Err(_) => Ok(None),
     }
   }
 
@@ -1579,20 +4056,19 @@ impl Machine {
     dxml: String,
     flags: u32,
     bandwidth: BigInt,
-  ) -> Option<u32> {
-    let (_signed, bandwidth_u64, lossless) = bandwidth.get_u64();
-    if !lossless {
-      return None;
-    }
+  ) -> Result<Option<u32>> {
+    validate_migrate_uri(&dconn_uri)?;
+    validate_migrate_uri(&mig_uri)?;
+    let bandwidth_u64 = bigint_to_u64(&bandwidth)?;
     match self.domain.migrate_to_uri2(Some(&dconn_uri), Some(&mig_uri), Some(&dxml), flags, None, bandwidth_u64) {
-      Ok(_) => Some(0),
-      Err(_) =>None,
+      Ok(_) => Ok(Some(0)),
+      Err(_) => Ok(None),
     }
   }
 
   #[napi]
-  pub fn get_numa_parameters(&self, flags: u32) -> Option<crate::machine::NUMAParameters> {
-    match self.domain.get_numa_parameters(flags) {
+  pub fn get_numa_parameters(&self, flags: Option<u32>) -> Option<crate::machine::NUMAParameters> {
+    match self.domain.get_numa_parameters(flags.unwrap_or(0)) {
       Ok(result) => Some(NUMAParameters {
         node_set: result.node_set.map(|v| v.to_string()),
         mode: result.mode.map(|v| v as u32),
@@ -1673,6 +4149,46 @@ impl Machine {
     }
   }
 
+  /// Create a disk-only external snapshot, writing each listed disk to a new overlay file.
+  ///
+  /// # Arguments
+  ///
+  /// * `name` - The name of the snapshot.
+  /// * `disks` - The overlay to create for each disk. Disks not listed get the
+  ///   hypervisor's default snapshot handling.
+  /// * `flags` - Extra VirDomainSnapshotCreateFlags; `VirDomainSnapshotCreateDiskOnly`
+  ///   is always added.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `Snapshot` - The created snapshot.
+  /// * `null` - If there is an error during the snapshot creation.
+  ///
+  /// # Example (in JavaScript)
+  ///
+  /// ```javascript
+  /// const snapshot = machine.createExternalSnapshot('backup-2024-01-01', [
+  ///   { name: 'vda', file: '/var/lib/libvirt/images/vm.backup.qcow2', format: 'qcow2' },
+  /// ], VirDomainSnapshotCreateFlags.VirDomainSnapshotCreateAtomic);
+  /// // Copy the now read-only base image, then merge the overlay back with blockCommit.
+  /// ```
+  #[napi]
+  pub fn create_external_snapshot(
+    &self,
+    name: String,
+    disks: Vec<crate::snapshot::DiskSnapshotSpec>,
+    flags: u32,
+  ) -> Option<crate::snapshot::Snapshot> {
+    let disks: String = disks.iter().map(|disk| disk.to_xml()).collect();
+    let xml = format!(
+      "<domainsnapshot><name>{}</name><disks>{}</disks></domainsnapshot>",
+      crate::xml::escape(&name),
+      disks
+    );
+    self.snapshot_create_xml(xml, flags | virt::sys::VIR_DOMAIN_SNAPSHOT_CREATE_DISK_ONLY)
+  }
+
   /// List all snapshots of the domain.
   ///
   /// # Arguments
@@ -1704,8 +4220,8 @@ impl Machine {
   /// listSnapshots();
   /// ```
   #[napi]
-  pub fn list_all_snapshots(&self, flags: u32) -> Option<Vec<crate::snapshot::Snapshot>> {
-    match self.domain.list_all_snapshots(flags) {
+  pub fn list_all_snapshots(&self, flags: Option<u32>) -> Option<Vec<crate::snapshot::Snapshot>> {
+    match self.domain.list_all_snapshots(flags.unwrap_or(0)) {
       Ok(snapshots) => {
         let mut result = Vec::new();
         for snapshot in snapshots {
@@ -1746,8 +4262,8 @@ impl Machine {
   /// findSnapshot();
   /// ```
   #[napi]
-  pub fn snapshot_lookup_by_name(&self, name: String, flags: u32) -> Option<crate::snapshot::Snapshot> {
-    match virt::domain_snapshot::DomainSnapshot::lookup_by_name(&self.domain, &name, flags) {
+  pub fn snapshot_lookup_by_name(&self, name: String, flags: Option<u32>) -> Option<crate::snapshot::Snapshot> {
+    match virt::domain_snapshot::DomainSnapshot::lookup_by_name(&self.domain, &name, flags.unwrap_or(0)) {
       Ok(snapshot) => Some(crate::snapshot::Snapshot::from_domain_snapshot(snapshot)),
       Err(_) => None,
     }
@@ -1820,8 +4336,8 @@ impl Machine {
   /// getCurrentSnapshot();
   /// ```
   #[napi]
-  pub fn snapshot_current(&self, flags: u32) -> Option<crate::snapshot::Snapshot> {
-    match virt::domain_snapshot::DomainSnapshot::current(&self.domain, flags) {
+  pub fn snapshot_current(&self, flags: Option<u32>) -> Option<crate::snapshot::Snapshot> {
+    match virt::domain_snapshot::DomainSnapshot::current(&self.domain, flags.unwrap_or(0)) {
       Ok(snapshot) => Some(crate::snapshot::Snapshot::from_domain_snapshot(snapshot)),
       Err(_) => None,
     }
@@ -1839,11 +4355,10 @@ impl Machine {
   /// * `Boolean` - true if the domain has a current snapshot, false otherwise.
   /// * `null` - If there is an error during the check.
   #[napi]
-  pub fn has_current_snapshot(&self, flags: u32) -> Option<bool> {
-    // Try to get current snapshot, if it exists return true
-    match virt::domain_snapshot::DomainSnapshot::current(&self.domain, flags) {
-      Ok(_) => Some(true),
-      Err(_) => Some(false),
+  pub fn has_current_snapshot(&self, flags: Option<u32>) -> Option<bool> {
+    match unsafe { virt::sys::virDomainHasCurrentSnapshot(self.domain.as_ptr(), flags.unwrap_or(0)) } {
+      -1 => None,
+      ret => Some(ret == 1),
     }
   }
 
@@ -1859,10 +4374,211 @@ impl Machine {
   /// * `Number` - The number of snapshots.
   /// * `null` - If there is an error.
   #[napi]
-  pub fn num_of_snapshots(&self, flags: u32) -> Option<u32> {
-    match virt::domain_snapshot::DomainSnapshot::num(&self.domain, flags) {
+  pub fn num_of_snapshots(&self, flags: Option<u32>) -> Option<u32> {
+    match virt::domain_snapshot::DomainSnapshot::num(&self.domain, flags.unwrap_or(0)) {
       Ok(num) => Some(num),
       Err(_) => None,
     }
   }
+
+  /// Check whether the domain's driver supports snapshots.
+  ///
+  /// The probe queries the snapshot API without creating any snapshot, so no
+  /// metadata is left behind when snapshots are unsupported.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `Boolean` - true if snapshots are supported, false otherwise.
+  /// * `null` - If there is an error unrelated to snapshot support.
+  #[napi]
+  pub fn supports_snapshots(&self) -> Option<bool> {
+    match virt::domain_snapshot::DomainSnapshot::num(&self.domain, 0) {
+      Ok(_) => Some(true),
+      Err(err) if is_unsupported_error(&err) => Some(false),
+      Err(_) => None,
+    }
+  }
+
+  /// Check whether the domain supports checkpoints (incremental backups).
+  ///
+  /// The probe reads the domain capabilities for the domain's emulator,
+  /// architecture, machine type and virtualization type, and looks for the
+  /// `<backup supported='yes'/>` feature.
+  ///
+  /// # Returns
+  ///
+  /// This function returns:
+  /// * `Boolean` - true if checkpoints are supported, false otherwise.
+  /// * `null` - If the domain capabilities could not be retrieved.
+  #[napi]
+  pub fn supports_checkpoints(&self) -> Option<bool> {
+    let caps = self.domain_capabilities()?;
+    Some(
+      caps
+        .find("features/backup")
+        .and_then(|backup| backup.attr("supported"))
+        == Some("yes"),
+    )
+  }
+
+  fn domain_capabilities(&self) -> Option<crate::xml::Element> {
+    let desc = crate::xml::parse(&self.domain.get_xml_desc(0).ok()?)?;
+    let os_type = desc.find("os/type");
+    let caps = self
+      .con
+      .get_connection()
+      .get_domain_capabilities(
+        desc.find("devices/emulator").map(|e| e.text()),
+        os_type.and_then(|t| t.attr("arch")),
+        os_type.and_then(|t| t.attr("machine")),
+        desc.attr("type"),
+        0,
+      )
+      .ok()?;
+    crate::xml::parse(&caps)
+  }
+}
+
+/// Whether `err` reports that the driver does not implement the operation.
+fn is_unsupported_error(err: &virt::error::Error) -> bool {
+  let code = err.code() as u32;
+  code == virt::sys::VIR_ERR_NO_SUPPORT as u32
+    || code == virt::sys::VIR_ERR_ARGUMENT_UNSUPPORTED as u32
+    || code == virt::sys::VIR_ERR_OPERATION_UNSUPPORTED as u32
+}
+
+/// Disk bus implied by the conventional target name prefix, e.g. `vdb` is virtio.
+fn disk_bus_for_target(target_dev: &str) -> Option<&'static str> {
+  if target_dev.starts_with("vd") {
+    Some("virtio")
+  } else if target_dev.starts_with("sd") {
+    Some("scsi")
+  } else if target_dev.starts_with("hd") {
+    Some("ide")
+  } else if target_dev.starts_with("xvd") {
+    Some("xen")
+  } else {
+    None
+  }
+}
+
+/// Checks that a migration URI starts with a scheme (`tcp:`, `qemu+ssh:`, ...),
+/// so an obviously wrong destination is reported up front rather than as an
+/// opaque migration failure.
+fn validate_migrate_uri(uri: &str) -> Result<()> {
+  let scheme = uri.split(':').next().unwrap_or_default();
+  let valid = uri.contains(':')
+    && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+    && scheme.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+  if !valid {
+    return Err(Error::new(
+      Status::InvalidArg,
+      format!("invalid migration URI '{}': expected scheme://host", uri),
+    ));
+  }
+  Ok(())
+}
+
+/// Human readable name of a `virDomainState`.
+fn state_label(state: u32) -> &'static str {
+  match state {
+    virt::sys::VIR_DOMAIN_RUNNING => "running",
+    virt::sys::VIR_DOMAIN_BLOCKED => "idle",
+    virt::sys::VIR_DOMAIN_PAUSED => "paused",
+    virt::sys::VIR_DOMAIN_SHUTDOWN => "in shutdown",
+    virt::sys::VIR_DOMAIN_SHUTOFF => "shut off",
+    virt::sys::VIR_DOMAIN_CRASHED => "crashed",
+    virt::sys::VIR_DOMAIN_PMSUSPENDED => "pmsuspended",
+    _ => "no state",
+  }
+}
+
+/// Human readable name of the reason code reported along with `state`.
+fn state_reason_label(state: u32, reason: i32) -> &'static str {
+  let reasons: &[&str] = match state {
+    virt::sys::VIR_DOMAIN_RUNNING => &[
+      "unknown",
+      "booted",
+      "migrated",
+      "restored",
+      "from snapshot",
+      "unpaused",
+      "migration canceled",
+      "save canceled",
+      "event wakeup",
+      "crashed",
+      "post-copy",
+      "post-copy failed",
+    ],
+    virt::sys::VIR_DOMAIN_PAUSED => &[
+      "unknown",
+      "user",
+      "migrating",
+      "saving",
+      "dumping",
+      "I/O error",
+      "watchdog",
+      "from snapshot",
+      "shutting down",
+      "creating snapshot",
+      "crashed",
+      "starting up",
+      "post-copy",
+      "post-copy failed",
+      "api error",
+    ],
+    virt::sys::VIR_DOMAIN_SHUTDOWN => &["unknown", "user"],
+    virt::sys::VIR_DOMAIN_SHUTOFF => &[
+      "unknown",
+      "shutdown",
+      "destroyed",
+      "crashed",
+      "migrated",
+      "saved",
+      "failed",
+      "from snapshot",
+      "daemon",
+    ],
+    virt::sys::VIR_DOMAIN_CRASHED => &["unknown", "panicked"],
+    _ => &["unknown"],
+  };
+  usize::try_from(reason)
+    .ok()
+    .and_then(|reason| reasons.get(reason))
+    .copied()
+    .unwrap_or("unknown")
+}
+
+/// Convert a JS `BigInt` to `u64`. Negative values and values above
+/// `2^64 - 1` are rejected with an `InvalidArg` error instead of being
+/// silently wrapped or truncated.
+pub(crate) fn bigint_to_u64(value: &BigInt) -> Result<u64> {
+  let (signed, value_u64, lossless) = value.get_u64();
+  if signed {
+    return Err(Error::new(Status::InvalidArg, "negative value not allowed".to_string()));
+  }
+  if !lossless {
+    return Err(Error::new(Status::InvalidArg, "value exceeds u64 range".to_string()));
+  }
+  Ok(value_u64)
+}
+
+/// Convert a JS `BigInt` to `i64`, failing instead of silently truncating.
+pub(crate) fn bigint_to_i64(value: &BigInt) -> Result<i64> {
+  let (value_i64, lossless) = value.get_i64();
+  if !lossless {
+    return Err(Error::new(Status::InvalidArg, "value exceeds i64 range".to_string()));
+  }
+  Ok(value_i64)
+}
+
+extern "C" {
+  fn free(ptr: *mut std::os::raw::c_void);
+}
+
+/// Frees memory libvirt allocated and handed over to the caller, e.g. the
+/// string returned by `virDomainGetSchedulerType`.
+pub(crate) unsafe fn free_libvirt_memory<T>(ptr: *mut T) {
+  free(ptr as *mut std::os::raw::c_void);
 }
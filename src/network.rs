@@ -1,8 +1,11 @@
+use std::ffi::CString;
+
 use napi;
 
 use virt;
 
 use crate::connection::Connection;
+use crate::network_port::NetworkPort;
 
 #[napi]
 pub struct Network {
@@ -60,8 +63,8 @@ impl Network {
   }
 
   #[napi]
-  pub fn get_xml_desc(&self, flags: u32) -> Option<String> {
-    match self.network.get_xml_desc(flags) {
+  pub fn get_xml_desc(&self, flags: Option<u32>) -> Option<String> {
+    match self.network.get_xml_desc(flags.unwrap_or(0)) {
       Ok(xml_desc) => Some(xml_desc),
       Err(_) => None,
     }
@@ -154,4 +157,20 @@ impl Network {
       Err(_) => None,
     }
   }
+
+  #[napi]
+  pub fn port_lookup_by_uuid_string(&self, uuid: String) -> Option<NetworkPort> {
+    let uuid = CString::new(uuid).ok()?;
+    NetworkPort::from_ptr(unsafe {
+      virt::sys::virNetworkPortLookupByUUIDString(self.network.as_ptr(), uuid.as_ptr())
+    })
+  }
+
+  #[napi]
+  pub fn port_create_xml(&self, xml: String, flags: u32) -> Option<NetworkPort> {
+    let xml = CString::new(xml).ok()?;
+    NetworkPort::from_ptr(unsafe {
+      virt::sys::virNetworkPortCreateXML(self.network.as_ptr(), xml.as_ptr(), flags)
+    })
+  }
 }
@@ -0,0 +1,126 @@
+use std::ffi::CStr;
+use std::os::raw::c_char;
+
+use napi::bindgen_prelude::*;
+
+/// Quality of service settings of a network port. Rates are in kilobytes per
+/// second and bursts in kilobytes; fields left out are not changed.
+#[napi]
+pub struct NetworkPortParameters {
+  /// Average inbound bit rate.
+  pub inbound_average: Option<u32>,
+  /// Maximum inbound rate.
+  pub inbound_peak: Option<u32>,
+  /// Amount of data that can be received in a single burst at peak rate.
+  pub inbound_burst: Option<u32>,
+  /// Minimum inbound rate guaranteed to the port.
+  pub inbound_floor: Option<u32>,
+  /// Average outbound bit rate.
+  pub outbound_average: Option<u32>,
+  /// Maximum outbound rate.
+  pub outbound_peak: Option<u32>,
+  /// Amount of data that can be sent in a single burst at peak rate.
+  pub outbound_burst: Option<u32>,
+}
+
+impl FromNapiValue for NetworkPortParameters {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let obj = JsObject::from_napi_value(env, napi_val)?;
+    Ok(Self {
+      inbound_average: obj.get("inboundAverage")?,
+      inbound_peak: obj.get("inboundPeak")?,
+      inbound_burst: obj.get("inboundBurst")?,
+      inbound_floor: obj.get("inboundFloor")?,
+      outbound_average: obj.get("outboundAverage")?,
+      outbound_peak: obj.get("outboundPeak")?,
+      outbound_burst: obj.get("outboundBurst")?,
+    })
+  }
+}
+
+/// The attachment of a guest NIC (or other consumer) to a virtual network.
+#[napi]
+pub struct NetworkPort {
+  port: virt::sys::virNetworkPortPtr,
+}
+
+impl Drop for NetworkPort {
+  fn drop(&mut self) {
+    unsafe {
+      virt::sys::virNetworkPortFree(self.port);
+    }
+  }
+}
+
+#[napi]
+impl NetworkPort {
+  pub(crate) fn from_ptr(port: virt::sys::virNetworkPortPtr) -> Option<NetworkPort> {
+    if port.is_null() {
+      return None;
+    }
+    Some(NetworkPort { port })
+  }
+
+  #[napi]
+  pub fn get_uuid_string(&self) -> Option<String> {
+    let mut uuid: [c_char; virt::sys::VIR_UUID_STRING_BUFLEN as usize] =
+      [0; virt::sys::VIR_UUID_STRING_BUFLEN as usize];
+    if unsafe { virt::sys::virNetworkPortGetUUIDString(self.port, uuid.as_mut_ptr()) } < 0 {
+      return None;
+    }
+    Some(unsafe { CStr::from_ptr(uuid.as_ptr()) }.to_string_lossy().into_owned())
+  }
+
+  #[napi]
+  pub fn get_xml_desc(&self, flags: Option<u32>) -> Option<String> {
+    let xml = unsafe { virt::sys::virNetworkPortGetXMLDesc(self.port, flags.unwrap_or(0)) };
+    if xml.is_null() {
+      return None;
+    }
+    let result = unsafe { CStr::from_ptr(xml) }.to_string_lossy().into_owned();
+    unsafe { crate::machine::free_libvirt_memory(xml) };
+    Some(result)
+  }
+
+  /// Change the quality of service settings of the port.
+  ///
+  /// # Example (in JavaScript)
+  ///
+  /// ```javascript
+  /// const port = network.portLookupByUuidString('5a1d5b5e-9b36-4b1b-8d4c-6f1f2b0c9e0a');
+  /// port.setParameters({ inboundAverage: 1000, outboundAverage: 1000 }, 0);
+  /// ```
+  #[napi]
+  pub fn set_parameters(&self, params: NetworkPortParameters, flags: u32) -> Option<u32> {
+    let fields = [
+      ("inbound.average", params.inbound_average),
+      ("inbound.peak", params.inbound_peak),
+      ("inbound.burst", params.inbound_burst),
+      ("inbound.floor", params.inbound_floor),
+      ("outbound.average", params.outbound_average),
+      ("outbound.peak", params.outbound_peak),
+      ("outbound.burst", params.outbound_burst),
+    ];
+
+    let mut typed = crate::typed_params::Builder::new();
+    for (field, value) in fields {
+      if let Some(value) = value {
+        typed.add_uint(field, value)?;
+      }
+    }
+
+    let ret = unsafe { virt::sys::virNetworkPortSetParameters(self.port, typed.as_ptr(), typed.len(), flags) };
+    if ret < 0 {
+      return None;
+    }
+    Some(0)
+  }
+
+  #[napi]
+  pub fn delete(&self, flags: u32) -> Option<u32> {
+    match unsafe { virt::sys::virNetworkPortDelete(self.port, flags) } {
+      -1 => None,
+      _ => Some(0),
+    }
+  }
+}
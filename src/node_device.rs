@@ -1,11 +1,16 @@
+use std::ffi::CString;
+
 use napi;
 use virt;
 
+use crate::connection::Connection;
+
 #[napi]
 pub struct NodeDevice {
 	node: virt::nodedev::NodeDevice
 }
 
+#[napi]
 impl NodeDevice {
 	pub fn get(&self) -> &virt::nodedev::NodeDevice {
 		&self.node
@@ -14,4 +19,133 @@ impl NodeDevice {
 	pub fn from_node(node: virt::nodedev::NodeDevice) -> Self {
 		Self { node: node }
 	}
-}
\ No newline at end of file
+
+	#[napi]
+	pub fn lookup_by_name(conn: &Connection, name: String) -> Option<NodeDevice> {
+		match virt::nodedev::NodeDevice::lookup_by_name(conn.get_connection(), &name) {
+			Ok(node) => Some(NodeDevice::from_node(node)),
+			Err(_) => None,
+		}
+	}
+
+	#[napi]
+	pub fn get_name(&self) -> Option<String> {
+		match self.node.get_name() {
+			Ok(name) => Some(name),
+			Err(_) => None,
+		}
+	}
+
+	#[napi]
+	pub fn get_parent(&self) -> Option<String> {
+		match self.node.get_parent() {
+			Ok(parent) => Some(parent),
+			Err(_) => None,
+		}
+	}
+
+	#[napi]
+	pub fn get_xml_desc(&self, flags: Option<u32>) -> Option<String> {
+		match self.node.get_xml_desc(flags.unwrap_or(0)) {
+			Ok(xml) => Some(xml),
+			Err(_) => None,
+		}
+	}
+
+	#[napi]
+	pub fn num_of_caps(&self) -> Option<u32> {
+		match self.node.num_of_caps() {
+			Ok(num) => Some(num as u32),
+			Err(_) => None,
+		}
+	}
+
+	/// Returns the capability names of the device, e.g. `pci`, `usb_device` or `net`.
+	#[napi]
+	pub fn list_caps(&self) -> Option<Vec<String>> {
+		match self.node.list_caps() {
+			Ok(caps) => Some(caps),
+			Err(_) => None,
+		}
+	}
+
+	/// Creates a transient device, e.g. a mediated device (mdev) such as a vGPU instance.
+	#[napi]
+	pub fn create_xml(conn: &Connection, xml: String, flags: u32) -> Option<NodeDevice> {
+		match virt::nodedev::NodeDevice::create_xml(conn.get_connection(), &xml, flags) {
+			Ok(node) => Some(NodeDevice::from_node(node)),
+			Err(_) => None,
+		}
+	}
+
+	/// Destroys a device created with `createXml`.
+	#[napi]
+	pub fn destroy(&self) -> Option<u32> {
+		match self.node.destroy() {
+			Ok(_) => Some(0),
+			Err(_) => None,
+		}
+	}
+
+	#[napi]
+	pub fn free(&mut self) -> Option<u32> {
+		match self.node.free() {
+			Ok(_) => Some(0),
+			Err(_) => None,
+		}
+	}
+
+	/// Detaches the device from its host driver so it can be assigned to a guest.
+	///
+	/// Spelled like the libvirt function `virNodeDeviceDettach`.
+	#[napi]
+	pub fn dettach(&self) -> Option<u32> {
+		match unsafe { virt::sys::virNodeDeviceDettach(self.node.as_ptr()) } {
+			-1 => None,
+			_ => Some(0),
+		}
+	}
+
+	/// Detaches the device from its host driver, choosing the stub driver.
+	///
+	/// # Arguments
+	///
+	/// * `driver_name` - The stub driver to bind to, e.g. `vfio`. An empty string lets libvirt pick.
+	/// * `flags` - Extra flags, currently unused by libvirt. Pass 0.
+	#[napi]
+	pub fn detach_flags(&self, driver_name: String, flags: u32) -> Option<u32> {
+		let driver = if driver_name.is_empty() {
+			None
+		} else {
+			Some(CString::new(driver_name).ok()?)
+		};
+		let ret = unsafe {
+			virt::sys::virNodeDeviceDetachFlags(
+				self.node.as_ptr(),
+				driver.as_ref().map_or(std::ptr::null(), |d| d.as_ptr()),
+				flags,
+			)
+		};
+		match ret {
+			-1 => None,
+			_ => Some(0),
+		}
+	}
+
+	/// Gives a previously detached device back to its host driver.
+	#[napi]
+	pub fn reattach(&self) -> Option<u32> {
+		match self.node.reattach() {
+			Ok(_) => Some(0),
+			Err(_) => None,
+		}
+	}
+
+	#[napi]
+	pub fn reset(&self) -> Option<u32> {
+		match self.node.reset() {
+			Ok(_) => Some(0),
+			Err(_) => None,
+		}
+	}
+}
@@ -51,8 +51,8 @@ impl NWFilter {
     }
 
     #[napi]
-    pub fn get_xml_desc(&self, flags: u32) -> Option<String> {
-        match self.nw_filter.get_xml_desc(flags) {
+    pub fn get_xml_desc(&self, flags: Option<u32>) -> Option<String> {
+        match self.nw_filter.get_xml_desc(flags.unwrap_or(0)) {
             Ok(xml) => Some(xml),
             Err(_) => None,
         }
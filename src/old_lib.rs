@@ -276,8 +276,8 @@ pub fn libvirt_power_off(ctx: CallContext) -> NapiResult<JsBoolean> {
   }
 }
 
-#[js_function(2)] // xml:str
-pub fn libvirt_define_xml(ctx: CallContext) -> NapiResult<JsBoolean> {
+#[js_function(2)] // xml:str, returns the name of the defined domain
+pub fn libvirt_define_xml(ctx: CallContext) -> NapiResult<JsString> {
   let this: JsObject = ctx.this_unchecked();
   let lib: &mut Libvirt = ctx.env.unwrap(&this)?;
   let xml = ctx.get::<JsString>(0)?.into_utf8()?.as_str()?.to_owned();
@@ -286,7 +286,13 @@ pub fn libvirt_define_xml(ctx: CallContext) -> NapiResult<JsBoolean> {
     Ok(connection) => {
         let result = Domain::define_xml(&connection, &xml.to_owned()); // Adjusted to two arguments
         match result {
-            Ok(_) => ctx.env.get_boolean(true),
+            Ok(domain) => match domain.get_name() {
+                Ok(name) => ctx.env.create_string(&name),
+                Err(err) => Err(napi::Error::new(
+                    napi::Status::GenericFailure,
+                    format!("Operation failed: {}", err)
+                )),
+            },
             Err(err) => {
                 Err(napi::Error::new(
                     napi::Status::GenericFailure,
@@ -384,7 +390,8 @@ fn init(mut exports: JsObject, env: Env) -> Result<(), napi::Error> {
             Property::new("listMachines")?.with_method(libvirt_list_machines),
             Property::new("suspendMachine")?.with_method(libvirt_suspend),
             Property::new("getDomainInfo")?.with_method(libvirt_get_domain_info),
-            Property::new("powerOn")?.with_method(libvirt_power_resume),
+            Property::new("powerOn")?.with_method(libvirt_power_on),
+            Property::new("resumeMachine")?.with_method(libvirt_power_resume),
             Property::new("powerOff")?.with_method(libvirt_power_off),
             Property::new("defineXML")?.with_method(libvirt_define_xml),
         ],
@@ -1,11 +1,15 @@
 use napi;
+use napi::bindgen_prelude::Buffer;
 use virt;
 
+use crate::connection::Connection;
+
 #[napi]
 pub struct Secret {
 	secret: virt::secret::Secret
 }
 
+#[napi]
 impl Secret {
 	pub fn get(&self) -> &virt::secret::Secret {
 		&self.secret
@@ -14,4 +18,90 @@ impl Secret {
 	pub fn from_secret(secret: virt::secret::Secret) -> Self {
 		Self { secret: secret }
 	}
-}
\ No newline at end of file
+
+	#[napi]
+	pub fn define_xml(conn: &Connection, xml: String, flags: u32) -> Option<Secret> {
+		match virt::secret::Secret::define_xml(conn.get_connection(), &xml, flags) {
+			Ok(secret) => Some(Secret::from_secret(secret)),
+			Err(_) => None,
+		}
+	}
+
+	#[napi]
+	pub fn lookup_by_uuid_string(conn: &Connection, uuid: String) -> Option<Secret> {
+		match virt::secret::Secret::lookup_by_uuid_string(conn.get_connection(), &uuid) {
+			Ok(secret) => Some(Secret::from_secret(secret)),
+			Err(_) => None,
+		}
+	}
+
+	/// Looks up a secret by what it protects, e.g. a volume path for
+	/// `VIR_SECRET_USAGE_TYPE_VOLUME` or a target name for iSCSI.
+	#[napi]
+	pub fn lookup_by_usage(conn: &Connection, usage_type: i32, usage_id: String) -> Option<Secret> {
+		match virt::secret::Secret::lookup_by_usage(conn.get_connection(), usage_type, &usage_id) {
+			Ok(secret) => Some(Secret::from_secret(secret)),
+			Err(_) => None,
+		}
+	}
+
+	#[napi]
+	pub fn undefine(&self) -> Option<u32> {
+		match self.secret.undefine() {
+			Ok(_) => Some(0),
+			Err(_) => None,
+		}
+	}
+
+	#[napi]
+	pub fn get_uuid_string(&self) -> Option<String> {
+		match self.secret.get_uuid_string() {
+			Ok(uuid) => Some(uuid),
+			Err(_) => None,
+		}
+	}
+
+	#[napi]
+	pub fn get_usage_id(&self) -> Option<String> {
+		match self.secret.get_usage_id() {
+			Ok(usage_id) => Some(usage_id),
+			Err(_) => None,
+		}
+	}
+
+	#[napi]
+	pub fn get_usage_type(&self) -> Option<u32> {
+		match self.secret.get_usage_type() {
+			Ok(usage_type) => Some(usage_type as u32),
+			Err(_) => None,
+		}
+	}
+
+	#[napi]
+	pub fn get_xml_desc(&self, flags: Option<u32>) -> Option<String> {
+		match self.secret.get_xml_desc(flags.unwrap_or(0)) {
+			Ok(xml) => Some(xml),
+			Err(_) => None,
+		}
+	}
+
+	/// Sets the secret value. The value is taken as raw bytes, so binary
+	/// keys survive unchanged.
+	#[napi]
+	pub fn set_value(&self, value: Buffer, flags: u32) -> Option<u32> {
+		match self.secret.set_value(&value, flags) {
+			Ok(_) => Some(0),
+			Err(_) => None,
+		}
+	}
+
+	/// Returns the raw bytes of the secret value, or `null` if it has no
+	/// value or is private.
+	#[napi]
+	pub fn get_value(&self, flags: u32) -> Option<Buffer> {
+		match self.secret.get_value(flags) {
+			Ok(value) => Some(Buffer::from(value)),
+			Err(_) => None,
+		}
+	}
+}
@@ -22,6 +22,43 @@ pub struct SnapshotInfo {
     pub has_metadata: bool,
 }
 
+/// An external snapshot of one disk: where the new overlay image goes.
+#[napi]
+pub struct DiskSnapshotSpec {
+    /// Disk target, e.g. `vda`
+    pub name: String,
+    /// Path of the overlay file to create
+    pub file: String,
+    /// Format of the overlay, e.g. `qcow2` (hypervisor default if omitted)
+    pub format: Option<String>,
+}
+
+impl FromNapiValue for DiskSnapshotSpec {
+    unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+        let obj = JsObject::from_napi_value(env, napi_val)?;
+        let name: String = obj.get("name")?.ok_or_else(|| Error::new(Status::InvalidArg, "missing disk name".to_string()))?;
+        let file: String = obj.get("file")?.ok_or_else(|| Error::new(Status::InvalidArg, "missing overlay file".to_string()))?;
+        let format: Option<String> = obj.get("format")?;
+        Ok(Self { name, file, format })
+    }
+}
+
+impl DiskSnapshotSpec {
+    /// Renders the `<disk>` element of a `<domainsnapshot>` document.
+    pub(crate) fn to_xml(&self) -> String {
+        let driver = match &self.format {
+            Some(format) => format!("<driver type='{}'/>", crate::xml::escape(format)),
+            None => String::new(),
+        };
+        format!(
+            "<disk name='{}' snapshot='external'>{}<source file='{}'/></disk>",
+            crate::xml::escape(&self.name),
+            driver,
+            crate::xml::escape(&self.file)
+        )
+    }
+}
+
 #[napi]
 impl Snapshot {
     pub fn from_domain_snapshot(snapshot: DomainSnapshot) -> Self {
@@ -60,6 +97,48 @@ impl Snapshot {
         }
     }
 
+    /// Get the name, description, creation time and flags of the snapshot in one call.
+    ///
+    /// # Returns
+    ///
+    /// This function returns:
+    /// * `SnapshotInfo` - If the snapshot could be inspected.
+    /// * `null` - If there is an error reading the snapshot.
+    ///
+    /// # Example (in JavaScript)
+    ///
+    /// ```javascript
+    /// const { Machine } = require('libvirt-node');
+    ///
+    /// async function showSnapshots() {
+    ///   const machine = await Machine.lookupByName(conn, 'your-domain-name');
+    ///   for (const snapshot of machine.listAllSnapshots(0) ?? []) {
+    ///     const info = snapshot.getInfo();
+    ///     console.log(info.name, new Date(info.creationTime * 1000), info.isCurrent ? '(current)' : '');
+    ///   }
+    /// }
+    ///
+    /// showSnapshots();
+    /// ```
+    #[napi]
+    pub fn get_info(&self) -> Option<SnapshotInfo> {
+        let xml = self.snapshot.get_xml_desc(0).ok()?;
+        let desc = crate::xml::parse(&xml)?;
+        Some(SnapshotInfo {
+            name: desc.child("name")?.text().to_string(),
+            description: desc
+                .child("description")
+                .map(|d| d.text().to_string())
+                .filter(|d| !d.is_empty()),
+            creation_time: desc
+                .child("creationTime")
+                .and_then(|t| t.text().parse().ok())
+                .unwrap_or(0),
+            is_current: self.snapshot.is_current(0).ok()?,
+            has_metadata: self.snapshot.has_metadata(0).ok()?,
+        })
+    }
+
     /// Get the XML description of the snapshot.
     ///
     /// # Arguments
@@ -89,8 +168,8 @@ impl Snapshot {
     /// getSnapshotXml();
     /// ```
     #[napi]
-    pub fn get_xml_desc(&self, flags: u32) -> Option<String> {
-        match self.snapshot.get_xml_desc(flags) {
+    pub fn get_xml_desc(&self, flags: Option<u32>) -> Option<String> {
+        match self.snapshot.get_xml_desc(flags.unwrap_or(0)) {
             Ok(xml) => Some(xml),
             Err(_) => None,
         }
@@ -134,6 +213,42 @@ impl Snapshot {
         }
     }
 
+    /// Revert the owning domain to this snapshot.
+    ///
+    /// # Arguments
+    ///
+    /// * `flags` - The flags to use for the revert. Use VirDomainSnapshotRevertFlags enum.
+    ///
+    /// # Returns
+    ///
+    /// This function returns:
+    /// * `0` - If the domain is reverted successfully.
+    /// * `null` - If there is an error during the revert, e.g. a risky revert
+    ///   attempted without `VirDomainSnapshotRevertForce`.
+    ///
+    /// # Example (in JavaScript)
+    ///
+    /// ```javascript
+    /// const { Machine, VirDomainSnapshotRevertFlags } = require('libvirt-node');
+    ///
+    /// async function rollback() {
+    ///   const machine = await Machine.lookupByName(conn, 'your-domain-name');
+    ///   const snapshot = await machine.snapshotLookupByName('snapshot-name', 0);
+    ///   if (snapshot) {
+    ///     snapshot.revert(VirDomainSnapshotRevertFlags.VirDomainSnapshotRevertRunning);
+    ///   }
+    /// }
+    ///
+    /// rollback();
+    /// ```
+    #[napi]
+    pub fn revert(&self, flags: u32) -> Option<u32> {
+        match self.snapshot.revert(flags) {
+            Ok(_) => Some(0),
+            Err(_) => None,
+        }
+    }
+
     /// Check if this snapshot is the current snapshot.
     ///
     /// # Arguments
@@ -163,8 +278,8 @@ impl Snapshot {
     /// checkCurrentSnapshot();
     /// ```
     #[napi]
-    pub fn is_current(&self, flags: u32) -> Option<bool> {
-        match self.snapshot.is_current(flags) {
+    pub fn is_current(&self, flags: Option<u32>) -> Option<bool> {
+        match self.snapshot.is_current(flags.unwrap_or(0)) {
             Ok(current) => Some(current),
             Err(_) => None,
         }
@@ -182,8 +297,8 @@ impl Snapshot {
     /// * `Boolean` - true if the snapshot has metadata, false otherwise.
     /// * `null` - If there is an error during the check.
     #[napi]
-    pub fn has_metadata(&self, flags: u32) -> Option<bool> {
-        match self.snapshot.has_metadata(flags) {
+    pub fn has_metadata(&self, flags: Option<u32>) -> Option<bool> {
+        match self.snapshot.has_metadata(flags.unwrap_or(0)) {
             Ok(has_meta) => Some(has_meta),
             Err(_) => None,
         }
@@ -201,13 +316,66 @@ impl Snapshot {
     /// * `Snapshot` - The parent snapshot.
     /// * `null` - If there is no parent or an error occurred.
     #[napi]
-    pub fn get_parent(&self, flags: u32) -> Option<Snapshot> {
-        match self.snapshot.get_parent(flags) {
+    pub fn get_parent(&self, flags: Option<u32>) -> Option<Snapshot> {
+        match self.snapshot.get_parent(flags.unwrap_or(0)) {
             Ok(parent) => Some(Snapshot::from_domain_snapshot(parent)),
             Err(_) => None,
         }
     }
 
+    /// Get the number of children of this snapshot.
+    ///
+    /// # Arguments
+    ///
+    /// * `flags` - See VirDomainSnapshotListFlags. `VirDomainSnapshotListRoots`
+    ///   counts all descendants instead of only the direct children.
+    ///
+    /// # Returns
+    ///
+    /// This function returns:
+    /// * `number` - The number of children.
+    /// * `null` - If there is an error during the operation.
+    #[napi]
+    pub fn num_children(&self, flags: Option<u32>) -> Option<u32> {
+        match self.snapshot.num_children(flags.unwrap_or(0)) {
+            Ok(count) => Some(count),
+            Err(_) => None,
+        }
+    }
+
+    /// List the children of this snapshot.
+    ///
+    /// # Arguments
+    ///
+    /// * `flags` - See VirDomainSnapshotListFlags. `VirDomainSnapshotListRoots`
+    ///   lists all descendants instead of only the direct children.
+    ///
+    /// # Returns
+    ///
+    /// This function returns:
+    /// * `Snapshot[]` - The children, empty for a leaf snapshot.
+    /// * `null` - If there is an error during the listing.
+    ///
+    /// # Example (in JavaScript)
+    ///
+    /// ```javascript
+    /// function printTree(snapshot, depth = 0) {
+    ///   console.log(`${'  '.repeat(depth)}${snapshot.getName()}`);
+    ///   for (const child of snapshot.listAllChildren(0)) {
+    ///     printTree(child, depth + 1);
+    ///   }
+    /// }
+    ///
+    /// machine.listAllSnapshots(VirDomainSnapshotListFlags.VirDomainSnapshotListRoots).forEach((root) => printTree(root));
+    /// ```
+    #[napi]
+    pub fn list_all_children(&self, flags: Option<u32>) -> Option<Vec<Snapshot>> {
+        match self.snapshot.list_all_children(flags.unwrap_or(0)) {
+            Ok(children) => Some(children.into_iter().map(Snapshot::from_domain_snapshot).collect()),
+            Err(_) => None,
+        }
+    }
+
     /// Free the snapshot object.
     ///
     /// # Returns
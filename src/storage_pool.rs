@@ -1,5 +1,18 @@
 use virt;
 
+/// State and size of a storage pool.
+#[napi]
+pub struct StoragePoolInfo {
+    /// The pool state. See VirStoragePoolState.
+    pub state: u32,
+    /// Logical size of the pool in bytes.
+    pub capacity: napi::bindgen_prelude::BigInt,
+    /// Bytes currently allocated in the pool.
+    pub allocation: napi::bindgen_prelude::BigInt,
+    /// Bytes still free for new volumes.
+    pub available: napi::bindgen_prelude::BigInt,
+}
+
 #[napi]
 pub struct StoragePool {
     storage_pool: virt::storage_pool::StoragePool
@@ -51,7 +64,14 @@ impl StoragePool {
         }
     }
 
-    // TODO: implement lookup_by_volume
+    /// Looks up the storage pool a volume belongs to.
+    #[napi]
+    pub fn lookup_by_volume(vol: &crate::storage_vol::StorageVol) -> Option<StoragePool> {
+        match virt::storage_pool::StoragePool::lookup_by_volume(vol.get_vol()) {
+            Ok(pool) => Some(StoragePool::from_storage_pool(pool)),
+            Err(_) => None,
+        }
+    }
 
     // lookup_by_uuid_string
     #[napi]
@@ -103,10 +123,25 @@ impl StoragePool {
         }
     }
 
-    // get_xml_desc
+    /// Returns the raw 16 byte UUID of the pool.
     #[napi]
-    pub fn get_xml_desc(&self) -> Option<String> {
-        match self.storage_pool.get_xml_desc(0) {
+    pub fn get_uuid(&self) -> Option<napi::bindgen_prelude::Buffer> {
+        let mut uuid = vec![0u8; virt::sys::VIR_UUID_BUFLEN as usize];
+        let ret = unsafe {
+            virt::sys::virStoragePoolGetUUID(self.storage_pool.as_ptr(), uuid.as_mut_ptr())
+        };
+        if ret < 0 {
+            return None;
+        }
+        Some(uuid.into())
+    }
+
+    /// Returns the XML description of the pool. Pass
+    /// `VirStorageXMLFlags.VirStorageXMLInactive` to get the persistent config
+    /// of an active pool, e.g. to edit and redefine it.
+    #[napi]
+    pub fn get_xml_desc(&self, flags: Option<u32>) -> Option<String> {
+        match self.storage_pool.get_xml_desc(flags.unwrap_or(0)) {
             Ok(xml) => Some(xml),
             Err(_) => None,
         }
@@ -171,7 +206,7 @@ impl StoragePool {
         }
     }
 
-    // TODO: create enum for this flags
+    /// Refreshes the list of volumes in the pool. See VirStoragePoolRefreshFlags.
     #[napi]
     pub fn refresh(&self, flags: u32) -> Option<u32> {
         match self.storage_pool.refresh(flags) {
@@ -196,6 +231,23 @@ impl StoragePool {
         }
     }
 
+    /// Returns the state of the pool and its sizes in bytes.
+    ///
+    /// Prefer this over `getInfo`, whose sizes are converted to JS numbers and
+    /// lose precision above 2^53 bytes.
+    #[napi]
+    pub fn get_info_typed(&self) -> Option<StoragePoolInfo> {
+        match self.storage_pool.get_info() {
+            Ok(info) => Some(StoragePoolInfo {
+                state: info.state as u32,
+                capacity: info.capacity.into(),
+                allocation: info.allocation.into(),
+                available: info.available.into(),
+            }),
+            Err(_) => None,
+        }
+    }
+
     // get_info -> return a json/hash object
     #[napi]
     pub fn get_info(&self) -> Option<serde_json::Value> {
@@ -211,4 +263,54 @@ impl StoragePool {
             Err(_) => None,
         }
     }
+
+    /// Creates a volume in the pool without having to write its XML.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the new volume, e.g. `mydisk.qcow2`.
+    /// * `capacity_bytes` - Logical size of the volume in bytes.
+    /// * `format` - Volume format, e.g. `raw` or `qcow2`.
+    /// * `flags` - Bitwise-OR of virStorageVolCreateFlags.
+    /// * `allocation_bytes` - Bytes to allocate up front. Pass `0n` for a thin
+    ///   (sparse) volume; leave it out to use the pool's default.
+    ///
+    /// # Returns
+    ///
+    /// The new StorageVol, or null on failure.
+    ///
+    /// # Example
+    ///
+    /// ```javascript
+    /// const pool = StoragePool.lookupByName(conn, 'default');
+    /// const GiB = 1024n ** 3n;
+    /// const raw = pool.createVolume('data.img', 10n * GiB, 'raw', 0);
+    /// const thin = pool.createVolume('disk.qcow2', 20n * GiB, 'qcow2', 0, 0n);
+    /// ```
+    #[napi]
+    pub fn create_volume(
+        &self,
+        name: String,
+        capacity_bytes: napi::bindgen_prelude::BigInt,
+        format: String,
+        flags: u32,
+        allocation_bytes: Option<napi::bindgen_prelude::BigInt>,
+    ) -> napi::Result<Option<crate::storage_vol::StorageVol>> {
+        let capacity = crate::machine::bigint_to_u64(&capacity_bytes)?;
+        let allocation = match allocation_bytes {
+            Some(allocation) => format!(
+                "  <allocation unit='bytes'>{}</allocation>\n",
+                crate::machine::bigint_to_u64(&allocation)?
+            ),
+            None => String::new(),
+        };
+        let xml = format!(
+            "<volume>\n  <name>{}</name>\n  <capacity unit='bytes'>{}</capacity>\n{}  <target>\n    <format type='{}'/>\n  </target>\n</volume>\n",
+            crate::xml::escape(&name),
+            capacity,
+            allocation,
+            crate::xml::escape(&format),
+        );
+        Ok(crate::storage_vol::StorageVol::create_xml(self, xml, flags))
+    }
 }
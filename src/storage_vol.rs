@@ -1,5 +1,7 @@
 use crate::connection::Connection;
+use crate::machine::bigint_to_u64;
 use crate::storage_pool::StoragePool;
+use crate::stream::Stream;
 use napi;
 use serde_json::json;
 use virt::storage_vol::StorageVol as Vol;
@@ -9,15 +11,49 @@ pub struct StorageVol {
     vol: Vol,
 }
 
+/// Type and size of a storage volume.
+#[napi]
+pub struct StorageVolInfo {
+    /// The volume type. See VirStorageVolType.
+    pub kind: u32,
+    /// Logical size of the volume in bytes.
+    pub capacity: napi::bindgen_prelude::BigInt,
+    /// Bytes currently allocated on the host for the volume.
+    pub allocation: napi::bindgen_prelude::BigInt,
+}
+
 #[napi]
 impl StorageVol {
+    pub fn get_vol(&self) -> &Vol {
+        &self.vol
+    }
+
+    /// Gets the storage pool this volume belongs to.
+    ///
+    /// # Returns
+    ///
+    /// This function returns:
+    /// * `StoragePool` - The pool containing the volume.
+    /// * `null` - If the pool could not be found.
+    ///
+    /// # Example
+    ///
+    /// ```javascript
+    /// const vol = StorageVol.lookupByPath(conn, '/var/lib/libvirt/images/disk.qcow2');
+    /// vol.getPool().refresh(0);
+    /// ```
+    #[napi]
+    pub fn get_pool(&self) -> Option<StoragePool> {
+        StoragePool::lookup_by_volume(self)
+    }
+
     /// Creates a new storage volume in the given storage pool.
     ///
     /// # Arguments
     ///
     /// * `pool` - A reference to the StoragePool where the volume will be created.
     /// * `xml` - The XML description of the storage volume to create.
-    /// * `flags` - Bitwise-OR of virStorageVolCreateFlags.
+    /// * `flags` - Bitwise-OR of VirStorageVolCreateFlags.
     ///
     /// # Returns
     ///
@@ -70,7 +106,7 @@ impl StorageVol {
     /// * `pool` - A reference to the StoragePool where the volume will be created.
     /// * `xml` - The XML description of the storage volume to create.
     /// * `vol` - The source StorageVol to clone from.
-    /// * `flags` - Bitwise-OR of virStorageVolCreateFlags.
+    /// * `flags` - Bitwise-OR of VirStorageVolCreateFlags.
     ///
     /// # Returns
     ///
@@ -119,7 +155,7 @@ impl StorageVol {
     ///
     /// # Arguments
     ///
-    /// * `flags` - Bitwise-OR of virStorageVolDeleteFlags.
+    /// * `flags` - Bitwise-OR of VirStorageVolDeleteFlags.
     ///
     /// # Returns
     ///
@@ -155,22 +191,97 @@ impl StorageVol {
         }
     }
 
-    // #[napi]
-    // pub fn download(&self, stream: i32, offset: BigInt, length: BigInt, flags: u32) -> napi::Result<()> {
-    //     match self.vol.download(stream, offset.get_u64().1, length.get_u64().1, flags) {
-    //         Ok(_) => Ok(()),
-    //         Err(e) => Err(Error::from_reason(e.to_string())),
-    //     }
-    // }
+    /// Downloads the content of the volume through a stream.
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - Position in the volume to start reading from.
+    /// * `length` - Number of bytes to read, or 0 for everything up to the end.
+    /// * `flags` - Bitwise-OR of virStorageVolDownloadFlags.
+    ///
+    /// # Returns
+    ///
+    /// A Stream to `recv` the data from, or null on failure.
+    ///
+    /// # Example
+    ///
+    /// ```javascript
+    /// const fs = require('fs');
+    ///
+    /// const vol = StorageVol.lookupByName(pool, 'mydisk.qcow2');
+    /// const stream = vol.download(0n, 0n, 0);
+    /// const out = fs.openSync('/tmp/mydisk.qcow2', 'w');
+    /// let chunk;
+    /// while ((chunk = stream.recv(1024 * 1024)) && chunk.length > 0) {
+    ///   fs.writeSync(out, chunk);
+    /// }
+    /// stream.finish();
+    /// fs.closeSync(out);
+    /// ```
+    #[napi]
+    pub fn download(
+        &self,
+        offset: napi::bindgen_prelude::BigInt,
+        length: napi::bindgen_prelude::BigInt,
+        flags: u32,
+    ) -> napi::Result<Option<Stream>> {
+        let (offset, length) = (bigint_to_u64(&offset)?, bigint_to_u64(&length)?);
+        let stream = match self.new_stream() {
+            Some(stream) => stream,
+            None => return Ok(None),
+        };
+        let ret = unsafe {
+            virt::sys::virStorageVolDownload(self.vol.as_ptr(), stream.get().as_ptr(), offset, length, flags)
+        };
+        if ret < 0 {
+            return Ok(None);
+        }
+        Ok(Some(stream))
+    }
+
+    /// Downloads the content of the volume into memory.
+    ///
+    /// Only meant for small volumes: the whole range is buffered before it is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - Position in the volume to start reading from (default: 0).
+    /// * `length` - Number of bytes to read, or 0 for everything up to the end (default: 0).
+    /// * `flags` - Bitwise-OR of virStorageVolDownloadFlags (default: 0).
+    ///
+    /// # Returns
+    ///
+    /// The downloaded bytes, or null on failure.
+    #[napi]
+    pub fn download_to_buffer(
+        &self,
+        offset: Option<napi::bindgen_prelude::BigInt>,
+        length: Option<napi::bindgen_prelude::BigInt>,
+        flags: Option<u32>,
+    ) -> napi::Result<Option<napi::bindgen_prelude::Buffer>> {
+        let stream = self.download(
+            offset.unwrap_or_else(|| 0u64.into()),
+            length.unwrap_or_else(|| 0u64.into()),
+            flags.unwrap_or(0),
+        )?;
+        Ok(stream.and_then(|stream| stream.drain()).map(Into::into))
+    }
+
+    fn new_stream(&self) -> Option<Stream> {
+        let conn = unsafe { virt::sys::virStorageVolGetConnect(self.vol.as_ptr()) };
+        Stream::on_connection(conn, 0)
+    }
 
     /// Retrieves information about a storage volume.
     ///
+    /// Prefer `getInfoTyped`, which returns the sizes as BigInt instead of strings.
+    ///
     /// # Returns
     ///
     /// A Result containing a JsObject with the following properties:
     /// * `type`: The type of the storage volume (u32).
-    /// * `capacity`: The total capacity of the storage volume in bytes (BigInt).
-    /// * `allocation`: The current allocation of the storage volume in bytes (BigInt).
+    /// * `capacity`: The total capacity of the storage volume in bytes (decimal string).
+    /// * `allocation`: The current allocation of the storage volume in bytes (decimal string).
     ///
     /// # Example
     ///
@@ -209,6 +320,31 @@ impl StorageVol {
         }
     }
 
+    /// Retrieves information about a storage volume.
+    ///
+    /// # Returns
+    ///
+    /// A StorageVolInfo with the volume type and its sizes in bytes, or null on failure.
+    ///
+    /// # Example
+    ///
+    /// ```javascript
+    /// const volume = StorageVol.lookupByName(pool, 'my_volume.qcow2');
+    /// const { kind, capacity, allocation } = volume.getInfoTyped();
+    /// console.log(`type ${kind}, ${allocation} of ${capacity} bytes allocated`);
+    /// ```
+    #[napi]
+    pub fn get_info_typed(&self) -> Option<StorageVolInfo> {
+        match self.vol.get_info() {
+            Ok(info) => Some(StorageVolInfo {
+                kind: info.kind as u32,
+                capacity: info.capacity.into(),
+                allocation: info.allocation.into(),
+            }),
+            Err(_) => None,
+        }
+    }
+
     /// Retrieves the name of the storage volume.
     ///
     /// # Returns
@@ -302,8 +438,8 @@ impl StorageVol {
     /// getVolumeXMLDesc().catch(console.error);
     /// ```
     #[napi]
-    pub fn get_xml_desc(&self, flags: u32) -> Option<String> {
-        match self.vol.get_xml_desc(flags) {
+    pub fn get_xml_desc(&self, flags: Option<u32>) -> Option<String> {
+        match self.vol.get_xml_desc(flags.unwrap_or(0)) {
             Ok(xml) => Some(xml),
             Err(_) => None,
         }
@@ -314,7 +450,7 @@ impl StorageVol {
     /// # Arguments
     ///
     /// * `capacity` - New capacity for the volume, in bytes.
-    /// * `flags` - Bitwise-OR of virStorageVolResizeFlags
+    /// * `flags` - Bitwise-OR of VirStorageVolResizeFlags
     ///
     /// # Returns
     ///
@@ -341,20 +477,57 @@ impl StorageVol {
     /// resizeVolume().catch(console.error);
     /// ```
     #[napi]
-    pub fn resize(&self, capacity: napi::bindgen_prelude::BigInt, flags: u32) -> Option<u32> {
-        match self.vol.resize(capacity.get_u64().1, flags) {
-            Ok(_) => Some(0),
-            Err(_) => None,
+    pub fn resize(&self, capacity: napi::bindgen_prelude::BigInt, flags: u32) -> napi::Result<Option<u32>> {
+        let capacity_u64 = bigint_to_u64(&capacity)?;
+        match self.vol.resize(capacity_u64, flags) {
+            Ok(_) => Ok(Some(0)),
+            Err(_) => Ok(None),
         }
     }
 
-    // #[napi]
-    // pub fn upload(&self, stream: i32, offset: BigInt, length: BigInt, flags: u32) -> napi::Result<()> {
-    //     match self.vol.upload(stream, offset.get_u64().1, length.get_u64().1, flags) {
-    //         Ok(_) => Ok(()),
-    //         Err(e) => Err(Error::from_reason(e.to_string())),
-    //     }
-    // }
+    /// Uploads new content into the volume through a stream.
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - Position in the volume to start writing at.
+    /// * `length` - Number of bytes to write, or 0 for everything up to the end.
+    /// * `flags` - Bitwise-OR of virStorageVolUploadFlags.
+    ///
+    /// # Returns
+    ///
+    /// A Stream to `send` the data on, or null on failure. Call `finish` on it once
+    /// all data has been sent.
+    ///
+    /// # Example
+    ///
+    /// ```javascript
+    /// const fs = require('fs');
+    ///
+    /// const data = fs.readFileSync('/tmp/disk.img');
+    /// const stream = vol.upload(0n, BigInt(data.length), 0);
+    /// stream.send(data);
+    /// stream.finish();
+    /// ```
+    #[napi]
+    pub fn upload(
+        &self,
+        offset: napi::bindgen_prelude::BigInt,
+        length: napi::bindgen_prelude::BigInt,
+        flags: u32,
+    ) -> napi::Result<Option<Stream>> {
+        let (offset, length) = (bigint_to_u64(&offset)?, bigint_to_u64(&length)?);
+        let stream = match self.new_stream() {
+            Some(stream) => stream,
+            None => return Ok(None),
+        };
+        let ret = unsafe {
+            virt::sys::virStorageVolUpload(self.vol.as_ptr(), stream.get().as_ptr(), offset, length, flags)
+        };
+        if ret < 0 {
+            return Ok(None);
+        }
+        Ok(Some(stream))
+    }
 
 
     /// Wipes a storage volume.
@@ -363,7 +536,7 @@ impl StorageVol {
     ///
     /// # Arguments
     ///
-    /// * `flags` - Bitwise-OR of virStorageVolWipeFlags
+    /// * `flags` - Bitwise-OR of VirStorageVolWipeFlags
     ///
     /// # Returns
     ///
@@ -568,8 +741,8 @@ impl StorageVol {
     ///
     /// # Arguments
     ///
-    /// * `algorithm` - The algorithm to use for wiping. See virStorageVolWipeAlgorithm for possible values.
-    /// * `flags` - Bitwise-OR of virStorageVolWipeFlags.
+    /// * `algorithm` - The algorithm to use for wiping. See VirStorageVolWipeAlgorithm for possible values.
+    /// * `flags` - Bitwise-OR of VirStorageVolWipeFlags.
     ///
     /// # Returns
     ///
@@ -585,10 +758,9 @@ impl StorageVol {
     ///   const pool = await conn.storagePoolLookupByName('default');
     ///   const volume = await StorageVol.lookupByName(pool, 'volume_to_wipe.qcow2');
     ///
-    ///   // Wipe the volume using zero-fill algorithm
-    ///   // 0 is VIR_STORAGE_VOL_WIPE_ALG_ZERO
+    ///   // Wipe the volume using the DoD 5220.22-M algorithm
     ///   // 0 is passed as flags to use default behavior
-    ///   await volume.wipePattern(0, 0);
+    ///   await volume.wipePattern(libvirt.VirStorageVolWipeAlgorithm.VirStorageVolWipeAlgDod, 0);
     ///
     ///   console.log('Volume wiped successfully');
     ///
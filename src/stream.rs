@@ -0,0 +1,102 @@
+use napi::bindgen_prelude::Buffer;
+
+use crate::connection::Connection;
+
+const STREAM_CHUNK_SIZE: usize = 256 * 1024;
+
+/// A data stream between the client and a libvirt object, e.g. a guest console.
+///
+/// Streams are blocking: `recv` waits until data is available.
+#[napi]
+pub struct Stream {
+  stream: virt::stream::Stream,
+}
+
+#[napi]
+impl Stream {
+  pub fn get(&self) -> &virt::stream::Stream {
+    &self.stream
+  }
+
+  /// Creates a stream on the raw connection `conn`, e.g. the one owning a volume.
+  pub(crate) fn on_connection(conn: virt::sys::virConnectPtr, flags: u32) -> Option<Stream> {
+    let ptr = unsafe { virt::sys::virStreamNew(conn, flags) };
+    if ptr.is_null() {
+      return None;
+    }
+    Some(Stream {
+      stream: unsafe { virt::stream::Stream::from_ptr(ptr) },
+    })
+  }
+
+  /// Reads from the stream until the other end finishes it, then finishes
+  /// the stream on this side too.
+  pub(crate) fn drain(&self) -> Option<Vec<u8>> {
+    let mut data = Vec::new();
+    let mut chunk = vec![0u8; STREAM_CHUNK_SIZE];
+    loop {
+      match self.stream.recv(&mut chunk) {
+        Ok(0) => break,
+        Ok(read) => data.extend_from_slice(&chunk[..read]),
+        Err(_) => {
+          self.abort();
+          return None;
+        }
+      }
+    }
+    self.finish()?;
+    Some(data)
+  }
+
+  /// Create a new stream on the connection. See VirStreamFlags.
+  #[napi]
+  pub fn create(conn: &Connection, flags: Option<u32>) -> Option<Stream> {
+    match virt::stream::Stream::new(conn.get_connection(), flags.unwrap_or(0)) {
+      Ok(stream) => Some(Stream { stream }),
+      Err(_) => None,
+    }
+  }
+
+  /// Receive up to `nbytes` bytes from the stream.
+  ///
+  /// Blocks until data is available. Returns an empty buffer once the
+  /// other end has finished the stream, or `null` on error.
+  #[napi]
+  pub fn recv(&self, nbytes: u32) -> Option<Buffer> {
+    let mut data = vec![0u8; nbytes as usize];
+    match self.stream.recv(&mut data) {
+      Ok(read) => {
+        data.truncate(read);
+        Some(data.into())
+      }
+      Err(_) => None,
+    }
+  }
+
+  /// Send `data` on the stream. Returns the number of bytes written.
+  #[napi]
+  pub fn send(&self, data: Buffer) -> Option<u32> {
+    match self.stream.send(&data) {
+      Ok(written) => Some(written as u32),
+      Err(_) => None,
+    }
+  }
+
+  /// Complete the transfer successfully. The stream cannot be used afterwards.
+  #[napi]
+  pub fn finish(&self) -> Option<i32> {
+    match unsafe { virt::sys::virStreamFinish(self.stream.as_ptr()) } {
+      -1 => None,
+      ret => Some(ret),
+    }
+  }
+
+  /// Abort the transfer. The stream cannot be used afterwards.
+  #[napi]
+  pub fn abort(&self) -> Option<i32> {
+    match unsafe { virt::sys::virStreamAbort(self.stream.as_ptr()) } {
+      -1 => None,
+      ret => Some(ret),
+    }
+  }
+}
@@ -0,0 +1,189 @@
+//! Worker thread tasks backing the `*Async` methods.
+//!
+//! libvirt handles may be used from any thread, but the `virt` types wrap raw
+//! pointers and are therefore not `Send`. Every task takes its own reference
+//! on the handles it needs (`virDomainRef` / `virConnectRef`), so they stay
+//! valid even if the JS object is freed while the task runs, and only that
+//! task touches them on the worker thread. The JS value is built back on the
+//! main thread in `resolve`.
+//!
+//! Only async tasks are serialized: tasks started on the same connection run
+//! one at a time, because each holds the connection's lock (see
+//! `connection_lock`) for the whole libvirt call, so a long migration is never
+//! interleaved with another async job on that connection. Synchronous methods
+//! do not take the lock and can run while a task is in progress; libvirt
+//! connections are thread safe, so this only affects ordering.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock, Weak};
+
+use napi::{Env, Result, Task};
+use virt::domain::Domain;
+
+use crate::connection::Connection;
+use crate::machine::Machine;
+
+/// Moves a libvirt handle to a worker thread. See the module docs for why
+/// this is sound; only libvirt handles are allowed across.
+struct Shared<T>(T);
+
+unsafe impl Send for Shared<Domain> {}
+unsafe impl Send for Shared<Connection> {}
+
+/// Returns the lock serializing async tasks on the connection `conn`.
+///
+/// The map only holds weak references: a lock lives as long as a task holds
+/// or waits for it, and stale entries are pruned on every lookup, so closed
+/// connections do not accumulate.
+fn connection_lock(conn: virt::sys::virConnectPtr) -> Arc<Mutex<()>> {
+  static LOCKS: OnceLock<Mutex<HashMap<usize, Weak<Mutex<()>>>>> = OnceLock::new();
+  let mut locks = LOCKS
+    .get_or_init(|| Mutex::new(HashMap::new()))
+    .lock()
+    .unwrap_or_else(|poisoned| poisoned.into_inner());
+  locks.retain(|_, lock| lock.strong_count() > 0);
+  if let Some(lock) = locks.get(&(conn as usize)).and_then(Weak::upgrade) {
+    return lock;
+  }
+  let lock = Arc::new(Mutex::new(()));
+  locks.insert(conn as usize, Arc::downgrade(&lock));
+  lock
+}
+
+/// Runs `job` on `domain` while holding the lock of the domain's connection.
+fn with_domain_lock<T>(domain: &Domain, job: impl FnOnce(&Domain) -> T) -> T {
+  let conn = unsafe { virt::sys::virDomainGetConnect(domain.as_ptr()) };
+  let lock = connection_lock(conn);
+  let _guard = lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+  job(domain)
+}
+
+pub struct CreateTask {
+  domain: Shared<Domain>,
+}
+
+impl CreateTask {
+  pub(crate) fn new(domain: Domain) -> Self {
+    CreateTask {
+      domain: Shared(domain),
+    }
+  }
+}
+
+impl Task for CreateTask {
+  type Output = Option<u32>;
+  type JsValue = Option<u32>;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    Ok(with_domain_lock(&self.domain.0, |domain| domain.create().ok()))
+  }
+
+  fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output)
+  }
+}
+
+pub struct ManagedSaveTask {
+  domain: Shared<Domain>,
+  flags: u32,
+}
+
+impl ManagedSaveTask {
+  pub(crate) fn new(domain: Domain, flags: u32) -> Self {
+    ManagedSaveTask {
+      domain: Shared(domain),
+      flags,
+    }
+  }
+}
+
+impl Task for ManagedSaveTask {
+  type Output = Option<u32>;
+  type JsValue = Option<u32>;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    let flags = self.flags;
+    Ok(with_domain_lock(&self.domain.0, |domain| domain.managed_save(flags).ok()))
+  }
+
+  fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output)
+  }
+}
+
+pub struct CoreDumpTask {
+  domain: Shared<Domain>,
+  to: String,
+  flags: u32,
+}
+
+impl CoreDumpTask {
+  pub(crate) fn new(domain: Domain, to: String, flags: u32) -> Self {
+    CoreDumpTask {
+      domain: Shared(domain),
+      to,
+      flags,
+    }
+  }
+}
+
+impl Task for CoreDumpTask {
+  type Output = Option<u32>;
+  type JsValue = Option<u32>;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    let (to, flags) = (&self.to, self.flags);
+    Ok(with_domain_lock(&self.domain.0, |domain| domain.core_dump(to, flags).ok()))
+  }
+
+  fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output)
+  }
+}
+
+pub struct MigrateTask {
+  domain: Shared<Domain>,
+  dconn: Shared<Connection>,
+  flags: u32,
+  uri: String,
+  bandwidth: u64,
+  /// The domain on the destination, handed from `compute` to `resolve`.
+  migrated: Option<Shared<Domain>>,
+}
+
+impl MigrateTask {
+  pub(crate) fn new(domain: Domain, dconn: Connection, flags: u32, uri: String, bandwidth: u64) -> Self {
+    MigrateTask {
+      domain: Shared(domain),
+      dconn: Shared(dconn),
+      flags,
+      uri,
+      bandwidth,
+      migrated: None,
+    }
+  }
+}
+
+impl Task for MigrateTask {
+  type Output = ();
+  type JsValue = Option<Machine>;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    let dconn = self.dconn.0.get_connection();
+    let (flags, uri, bandwidth) = (self.flags, &self.uri, self.bandwidth);
+    self.migrated = with_domain_lock(&self.domain.0, |domain| {
+      domain
+        .migrate(dconn, flags, None, Some(uri), bandwidth)
+        .ok()
+        .map(Shared)
+    });
+    Ok(())
+  }
+
+  fn resolve(&mut self, _env: Env, _output: Self::Output) -> Result<Self::JsValue> {
+    Ok(self
+      .migrated
+      .take()
+      .map(|domain| Machine::from_shared_domain(domain.0, self.dconn.0.share())))
+  }
+}
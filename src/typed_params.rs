@@ -0,0 +1,261 @@
+use std::ffi::{CStr, CString};
+use std::os::raw::c_int;
+
+use serde_json::{Map, Value};
+use virt::sys;
+
+/// A typed parameter value copied out of a libvirt `virTypedParameter` list.
+#[derive(Clone, Debug)]
+pub enum TypedParamValue {
+  Int(i32),
+  UInt(u32),
+  LLong(i64),
+  ULLong(u64),
+  Double(f64),
+  Boolean(bool),
+  String(String),
+}
+
+impl TypedParamValue {
+  pub fn as_i64(&self) -> Option<i64> {
+    match self {
+      TypedParamValue::Int(v) => Some(*v as i64),
+      TypedParamValue::UInt(v) => Some(*v as i64),
+      TypedParamValue::LLong(v) => Some(*v),
+      TypedParamValue::ULLong(v) => i64::try_from(*v).ok(),
+      _ => None,
+    }
+  }
+
+  pub fn as_u64(&self) -> Option<u64> {
+    match self {
+      TypedParamValue::Int(v) => u64::try_from(*v).ok(),
+      TypedParamValue::UInt(v) => Some(*v as u64),
+      TypedParamValue::LLong(v) => u64::try_from(*v).ok(),
+      TypedParamValue::ULLong(v) => Some(*v),
+      _ => None,
+    }
+  }
+
+  pub fn to_json(&self) -> Value {
+    match self {
+      TypedParamValue::Int(v) => Value::from(*v),
+      TypedParamValue::UInt(v) => Value::from(*v),
+      TypedParamValue::LLong(v) => Value::from(*v),
+      TypedParamValue::ULLong(v) => Value::from(*v),
+      TypedParamValue::Double(v) => Value::from(*v),
+      TypedParamValue::Boolean(v) => Value::from(*v),
+      TypedParamValue::String(v) => Value::from(v.clone()),
+    }
+  }
+}
+
+/// Copies a raw typed parameter list into owned `(field, value)` pairs.
+///
+/// # Safety
+///
+/// `params` must point to `nparams` initialized parameters, or be null.
+pub unsafe fn from_raw(
+  params: sys::virTypedParameterPtr,
+  nparams: i32,
+) -> Vec<(String, TypedParamValue)> {
+  let mut result = Vec::new();
+  if params.is_null() {
+    return result;
+  }
+  for i in 0..nparams as isize {
+    let param = &*params.offset(i);
+    let field = CStr::from_ptr(param.field.as_ptr())
+      .to_string_lossy()
+      .into_owned();
+    let value = match param.type_ as u32 {
+      sys::VIR_TYPED_PARAM_INT => TypedParamValue::Int(param.value.i),
+      sys::VIR_TYPED_PARAM_UINT => TypedParamValue::UInt(param.value.ui),
+      sys::VIR_TYPED_PARAM_LLONG => TypedParamValue::LLong(param.value.l),
+      sys::VIR_TYPED_PARAM_ULLONG => TypedParamValue::ULLong(param.value.ul),
+      sys::VIR_TYPED_PARAM_DOUBLE => TypedParamValue::Double(param.value.d),
+      sys::VIR_TYPED_PARAM_BOOLEAN => TypedParamValue::Boolean(param.value.b != 0),
+      sys::VIR_TYPED_PARAM_STRING => {
+        if param.value.s.is_null() {
+          TypedParamValue::String(String::new())
+        } else {
+          TypedParamValue::String(CStr::from_ptr(param.value.s).to_string_lossy().into_owned())
+        }
+      }
+      _ => continue,
+    };
+    result.push((field, value));
+  }
+  result
+}
+
+/// Finds the value of `field` in a parameter list.
+pub fn find<'a>(params: &'a [(String, TypedParamValue)], field: &str) -> Option<&'a TypedParamValue> {
+  params
+    .iter()
+    .find(|(name, _)| name == field)
+    .map(|(_, value)| value)
+}
+
+/// Builds a nested JSON object from a parameter list, splitting field names
+/// on `.`: `vcpu.0.state` ends up as `{ "vcpu": { "0": { "state": ... } } }`.
+///
+/// If a field name is both a value and a prefix of other fields, the value
+/// wins and the longer fields are kept flat under the remaining path.
+pub fn to_nested_json(params: &[(String, TypedParamValue)]) -> Value {
+  let mut root = Map::new();
+  for (field, value) in params {
+    let mut node = &mut root;
+    let mut parts = field.split('.').peekable();
+    while let Some(part) = parts.next() {
+      if parts.peek().is_none() {
+        node.insert(part.to_string(), value.to_json());
+        break;
+      }
+      if !matches!(node.get(part), None | Some(Value::Object(_))) {
+        let rest: Vec<&str> = parts.collect();
+        node.insert(format!("{}.{}", part, rest.join(".")), value.to_json());
+        break;
+      }
+      node = match node
+        .entry(part.to_string())
+        .or_insert_with(|| Value::Object(Map::new()))
+      {
+        Value::Object(child) => child,
+        _ => unreachable!(),
+      };
+    }
+  }
+  Value::Object(root)
+}
+
+/// Builds a libvirt typed parameter list to pass to a setter, e.g.
+/// `virDomainSetSchedulerParametersFlags`. The list is freed on drop.
+pub struct Builder {
+  params: sys::virTypedParameterPtr,
+  nparams: c_int,
+  maxparams: c_int,
+}
+
+impl Builder {
+  pub fn new() -> Self {
+    Builder {
+      params: std::ptr::null_mut(),
+      nparams: 0,
+      maxparams: 0,
+    }
+  }
+
+  pub fn add_uint(&mut self, field: &str, value: u32) -> Option<()> {
+    let field = CString::new(field).ok()?;
+    let ret = unsafe {
+      sys::virTypedParamsAddUInt(&mut self.params, &mut self.nparams, &mut self.maxparams, field.as_ptr(), value)
+    };
+    if ret < 0 {
+      return None;
+    }
+    Some(())
+  }
+
+  pub fn add_ullong(&mut self, field: &str, value: u64) -> Option<()> {
+    let field = CString::new(field).ok()?;
+    let ret = unsafe {
+      sys::virTypedParamsAddULLong(&mut self.params, &mut self.nparams, &mut self.maxparams, field.as_ptr(), value)
+    };
+    if ret < 0 {
+      return None;
+    }
+    Some(())
+  }
+
+  pub fn add_llong(&mut self, field: &str, value: i64) -> Option<()> {
+    let field = CString::new(field).ok()?;
+    let ret = unsafe {
+      sys::virTypedParamsAddLLong(&mut self.params, &mut self.nparams, &mut self.maxparams, field.as_ptr(), value)
+    };
+    if ret < 0 {
+      return None;
+    }
+    Some(())
+  }
+
+  pub fn add_boolean(&mut self, field: &str, value: bool) -> Option<()> {
+    let field = CString::new(field).ok()?;
+    let ret = unsafe {
+      sys::virTypedParamsAddBoolean(
+        &mut self.params,
+        &mut self.nparams,
+        &mut self.maxparams,
+        field.as_ptr(),
+        value as c_int,
+      )
+    };
+    if ret < 0 {
+      return None;
+    }
+    Some(())
+  }
+
+  pub fn add_string(&mut self, field: &str, value: &str) -> Option<()> {
+    let field = CString::new(field).ok()?;
+    let value = CString::new(value).ok()?;
+    let ret = unsafe {
+      sys::virTypedParamsAddString(
+        &mut self.params,
+        &mut self.nparams,
+        &mut self.maxparams,
+        field.as_ptr(),
+        value.as_ptr(),
+      )
+    };
+    if ret < 0 {
+      return None;
+    }
+    Some(())
+  }
+
+  pub fn as_ptr(&self) -> sys::virTypedParameterPtr {
+    self.params
+  }
+
+  pub fn len(&self) -> c_int {
+    self.nparams
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.nparams == 0
+  }
+}
+
+impl Default for Builder {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl Drop for Builder {
+  fn drop(&mut self) {
+    unsafe { sys::virTypedParamsFree(self.params, self.nparams) };
+  }
+}
+
+/// Reads a typed parameter list with libvirt's two-pass protocol: `get` is
+/// first called with a null list to learn the number of parameters, then
+/// with a list of that size. `get` returns libvirt's status code.
+pub fn fetch(
+  mut get: impl FnMut(sys::virTypedParameterPtr, &mut c_int) -> c_int,
+) -> Option<Vec<(String, TypedParamValue)>> {
+  let mut nparams: c_int = 0;
+  if get(std::ptr::null_mut(), &mut nparams) < 0 {
+    return None;
+  }
+  let mut params: Vec<sys::virTypedParameter> = (0..nparams)
+    .map(|_| unsafe { std::mem::zeroed() })
+    .collect();
+  if get(params.as_mut_ptr(), &mut nparams) < 0 {
+    return None;
+  }
+  let values = unsafe { from_raw(params.as_mut_ptr(), nparams) };
+  unsafe { sys::virTypedParamsClear(params.as_mut_ptr(), nparams) };
+  Some(values)
+}
@@ -0,0 +1,205 @@
+//! Minimal XML reader for the documents libvirt hands back (domain,
+//! snapshot and capabilities XML). It only builds an element tree; it does
+//! not validate against any schema.
+
+/// An XML element with its attributes, child elements and text content.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Element {
+  pub name: String,
+  pub attributes: Vec<(String, String)>,
+  pub children: Vec<Element>,
+  pub text: String,
+}
+
+impl Element {
+  /// Returns the value of the attribute `name`.
+  pub fn attr(&self, name: &str) -> Option<&str> {
+    self
+      .attributes
+      .iter()
+      .find(|(key, _)| key == name)
+      .map(|(_, value)| value.as_str())
+  }
+
+  /// Returns the first child element called `name`.
+  pub fn child(&self, name: &str) -> Option<&Element> {
+    self.children.iter().find(|child| child.name == name)
+  }
+
+  /// Returns every child element called `name`.
+  pub fn children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> + 'a {
+    self.children.iter().filter(move |child| child.name == name)
+  }
+
+  /// Follows a `/` separated path of child element names, e.g. `os/type`.
+  pub fn find(&self, path: &str) -> Option<&Element> {
+    path
+      .split('/')
+      .filter(|part| !part.is_empty())
+      .try_fold(self, |element, part| element.child(part))
+  }
+
+  /// Returns the text content of the element, without surrounding whitespace.
+  pub fn text(&self) -> &str {
+    self.text.trim()
+  }
+}
+
+/// Parses `xml` into its root element, or `None` if it is not well formed.
+pub fn parse(xml: &str) -> Option<Element> {
+  let mut stack: Vec<Element> = Vec::new();
+  let mut root: Option<Element> = None;
+  let mut rest = xml;
+
+  while !rest.is_empty() {
+    if let Some(comment) = rest.strip_prefix("<!--") {
+      let end = comment.find("-->")?;
+      rest = &comment[end + 3..];
+    } else if let Some(cdata) = rest.strip_prefix("<![CDATA[") {
+      let end = cdata.find("]]>")?;
+      if let Some(parent) = stack.last_mut() {
+        parent.text.push_str(&cdata[..end]);
+      }
+      rest = &cdata[end + 3..];
+    } else if rest.starts_with("<?") || rest.starts_with("<!") {
+      let end = rest.find('>')?;
+      rest = &rest[end + 1..];
+    } else if let Some(closing) = rest.strip_prefix("</") {
+      let end = closing.find('>')?;
+      let element = stack.pop()?;
+      if element.name != closing[..end].trim() {
+        return None;
+      }
+      attach(&mut stack, &mut root, element);
+      rest = &closing[end + 1..];
+    } else if let Some(opening) = rest.strip_prefix('<') {
+      let end = tag_end(opening)?;
+      let tag = opening[..end].trim_end();
+      let (tag, self_closing) = match tag.strip_suffix('/') {
+        Some(tag) => (tag, true),
+        None => (tag, false),
+      };
+      let element = parse_tag(tag)?;
+      if self_closing {
+        attach(&mut stack, &mut root, element);
+      } else {
+        stack.push(element);
+      }
+      rest = &opening[end + 1..];
+    } else {
+      let end = rest.find('<').unwrap_or(rest.len());
+      if let Some(parent) = stack.last_mut() {
+        parent.text.push_str(&unescape(&rest[..end]));
+      }
+      rest = &rest[end..];
+    }
+  }
+
+  if !stack.is_empty() {
+    return None;
+  }
+  root
+}
+
+/// Escapes text so it can be embedded in an attribute value or element body.
+pub fn escape(value: &str) -> String {
+  value
+    .replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+    .replace('"', "&quot;")
+    .replace('\'', "&apos;")
+}
+
+fn attach(stack: &mut Vec<Element>, root: &mut Option<Element>, element: Element) {
+  match stack.last_mut() {
+    Some(parent) => parent.children.push(element),
+    None => {
+      if root.is_none() {
+        *root = Some(element);
+      }
+    }
+  }
+}
+
+/// Finds the `>` closing a tag, ignoring any inside quoted attribute values.
+fn tag_end(tag: &str) -> Option<usize> {
+  let mut quote: Option<char> = None;
+  for (index, c) in tag.char_indices() {
+    match (quote, c) {
+      (None, '"') | (None, '\'') => quote = Some(c),
+      (Some(q), _) if q == c => quote = None,
+      (None, '>') => return Some(index),
+      _ => {}
+    }
+  }
+  None
+}
+
+fn parse_tag(tag: &str) -> Option<Element> {
+  let name_end = tag.find(char::is_whitespace).unwrap_or(tag.len());
+  let name = &tag[..name_end];
+  if name.is_empty() {
+    return None;
+  }
+
+  let mut attributes = Vec::new();
+  let mut rest = tag[name_end..].trim_start();
+  while !rest.is_empty() {
+    let eq = rest.find('=')?;
+    let key = rest[..eq].trim();
+    let value_part = rest[eq + 1..].trim_start();
+    let quote = value_part.chars().next()?;
+    if quote != '"' && quote != '\'' {
+      return None;
+    }
+    let value_end = value_part[1..].find(quote)? + 1;
+    attributes.push((key.to_string(), unescape(&value_part[1..value_end])));
+    rest = value_part[value_end + 1..].trim_start();
+  }
+
+  Some(Element {
+    name: name.to_string(),
+    attributes,
+    children: Vec::new(),
+    text: String::new(),
+  })
+}
+
+fn unescape(value: &str) -> String {
+  if !value.contains('&') {
+    return value.to_string();
+  }
+  let mut result = String::with_capacity(value.len());
+  let mut rest = value;
+  while let Some(start) = rest.find('&') {
+    result.push_str(&rest[..start]);
+    let entity_end = match rest[start..].find(';') {
+      Some(end) => start + end,
+      None => {
+        result.push_str(&rest[start..]);
+        return result;
+      }
+    };
+    let entity = &rest[start + 1..entity_end];
+    let decoded = match entity {
+      "lt" => Some('<'),
+      "gt" => Some('>'),
+      "amp" => Some('&'),
+      "quot" => Some('"'),
+      "apos" => Some('\''),
+      _ => entity
+        .strip_prefix("#x")
+        .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+        .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+        .and_then(char::from_u32),
+    };
+    match decoded {
+      Some(c) => result.push(c),
+      None => result.push_str(&rest[start..=entity_end]),
+    }
+    rest = &rest[entity_end + 1..];
+  }
+  result.push_str(rest);
+  result
+}